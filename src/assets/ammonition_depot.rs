@@ -5,7 +5,7 @@ use bevy::prelude::*;
 use derive_more::{Deref, DerefMut};
 use serde::Deserialize;
 
-use crate::assets::game_assets::GameAssets;
+use crate::assets::{game_assets::GameAssets, optional, AsteroidMaterial, SteeringSettings};
 
 /// Loaded as part of the [GameAssets] collection, then inserted as a [Resource].
 #[derive(Asset, Resource, Reflect, Deserialize, Debug, Clone, Deref, DerefMut)]
@@ -25,6 +25,78 @@ impl FromWorld for AmmonitionDepot {
 #[derive(Reflect, Deserialize, Debug, Clone)]
 pub struct AmmonitionInfo {
     pub texture_key: String,
+    #[serde(default)]
+    pub kind: AmmonitionKind,
+    /// speed of a projectile (not used by beams)
+    #[serde(default)]
     pub speed: f32,
+    /// lifetime of a projectile (not used by beams)
+    #[serde(default)]
     pub timeout: Duration,
+    /// how much of the shooters velocity is added to the projectile (0.0 none, 1.0 all of it)
+    #[serde(default)]
+    pub inherit_velocity: f32,
+    /// hit points removed from whatever is hit
+    #[serde(default = "AmmonitionInfo::default_damage")]
+    pub damage: u32,
+    /// steering behaviors of homing projectiles, not used by beams
+    #[serde(with = "optional", skip_serializing_if = "Option::is_none", default)]
+    pub steering: Option<SteeringSettings>,
+    /// effect on asteroid materials (materials not listed take full damage)
+    #[serde(default)]
+    pub effectiveness: BTreeMap<AsteroidMaterial, AmmonitionEffect>,
+}
+
+impl AmmonitionInfo {
+    fn default_damage() -> u32 {
+        1
+    }
+
+    pub fn effect_on(&self, material: AsteroidMaterial) -> AmmonitionEffect {
+        self.effectiveness
+            .get(&material)
+            .copied()
+            .unwrap_or_default()
+    }
+}
+
+/// The outcome of ammonition hitting a specific [AsteroidMaterial].
+#[derive(Reflect, Deserialize, Debug, Clone, Copy, Default, PartialEq)]
+pub enum AmmonitionEffect {
+    /// Full damage
+    #[default]
+    Full,
+    /// Damage multiplied by the given factor, fractions of a hit point add up over several hits
+    Reduced(f32),
+    /// Projectiles bounce off without doing any damage
+    Ricochet,
+    /// The ammonition is spent without doing any damage
+    NoEffect,
+}
+
+impl AmmonitionEffect {
+    /// The damage done, possibly only a fraction of a hit point, see
+    /// [crate::asteroid::PartialDamage].
+    pub fn damage(&self, damage: u32) -> f32 {
+        match self {
+            AmmonitionEffect::Full => damage as f32,
+            AmmonitionEffect::Reduced(factor) => damage as f32 * factor,
+            AmmonitionEffect::Ricochet | AmmonitionEffect::NoEffect => 0.0,
+        }
+    }
+}
+
+#[derive(Reflect, Deserialize, Debug, Clone, Default)]
+pub enum AmmonitionKind {
+    /// Spawns a discrete projectile for every shot.
+    #[default]
+    Projectile,
+    /// A continuous beam, active for as long as fire is held.
+    ///
+    /// The width and maximum length of the beam is taken from the `size` of the texture in the
+    /// [AmmonitionTextureCollection](super::AmmonitionTextureCollection).
+    Beam {
+        /// how often the beam hits whatever is in its path
+        hit_interval: Duration,
+    },
 }
@@ -1,10 +1,14 @@
 use std::collections::BTreeMap;
 
+use core::time::Duration;
+
 use bevy::prelude::*;
 use derive_more::{Deref, DerefMut};
 use serde::Deserialize;
 
-use crate::assets::{asteroid_selection::AsteroidSelection, game_assets::GameAssets, optional};
+use crate::assets::{
+    asteroid_selection::AsteroidSelection, game_assets::GameAssets, optional, SteeringSettings,
+};
 
 /// Loaded as part of the [crate::assets::AsteroidAssets] collection, then inserted as a [Resource].
 #[derive(Asset, Resource, Reflect, Deserialize, Debug, Clone, Deref, DerefMut)]
@@ -31,8 +35,26 @@ pub struct AsteroidPool {
     pub velocity: AsteroidSpeedRange,
     /// default rotation of the asteroid
     pub angular_velocity: AsteroidRotationSpeed,
-    /// how does asteroid behave when hit
+    /// steering behaviors of seeking asteroids
+    #[serde(with = "optional", skip_serializing_if = "Option::is_none", default)]
+    pub steering: Option<SteeringSettings>,
+    /// what the asteroid is made of, deciding the effect of ammonition
+    #[serde(default)]
+    pub material: AsteroidMaterial,
+    /// hits needed to destroy the asteroid, when hit by ammonition doing a single point of damage
+    #[serde(default = "AsteroidPool::default_hit_points")]
+    pub hit_points: u32,
+    /// how does asteroid behave when destroyed
     pub hit_behavior: Vec<AsteroidHitBehavior>,
+    /// how does asteroid behave when hit, without being destroyed
+    #[serde(default)]
+    pub damage_behavior: Vec<AsteroidHitBehavior>,
+}
+
+impl AsteroidPool {
+    fn default_hit_points() -> u32 {
+        1
+    }
 }
 
 #[derive(Deserialize, Debug, Reflect, Clone)]
@@ -52,6 +74,16 @@ pub enum AsteroidTextureSelection {
     },
 }
 
+#[derive(
+    Deserialize, Debug, Reflect, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash,
+)]
+pub enum AsteroidMaterial {
+    #[default]
+    Rock,
+    Ice,
+    Metal,
+}
+
 #[derive(Deserialize, Debug, Reflect, Clone)]
 pub enum AsteroidHitBehavior {
     None,
@@ -59,9 +91,16 @@ pub enum AsteroidHitBehavior {
     Split {
         count: AsteroidSplitCount,
         select_from: Vec<AsteroidSelection>,
+        #[serde(default)]
+        velocity: AsteroidSplitVelocity,
     },
     Despawn,
     Audio(String),
+    /// Briefly tint the asteroid with the given color
+    Flash {
+        color: Color,
+        duration: Duration,
+    },
 }
 
 #[derive(Deserialize, Debug, Reflect, Clone, Copy)]
@@ -70,6 +109,32 @@ pub enum AsteroidSplitCount {
     Range { start: usize, end: usize },
 }
 
+/// How the velocities of asteroids created by a [AsteroidHitBehavior::Split] are chosen.
+#[derive(Deserialize, Debug, Reflect, Clone, Copy, Default)]
+pub enum AsteroidSplitVelocity {
+    /// Each child moves in a random direction, with a speed taken from its pool
+    #[default]
+    Random,
+    /// Children inherit the velocity of the parent and move away along the impact direction
+    Momentum {
+        /// share of the parent's velocity inherited by each child
+        #[serde(default = "AsteroidSplitVelocity::default_parent_factor")]
+        parent_factor: f32,
+        /// maximum angle in radians between the impact direction and the direction of a child
+        spread: f32,
+        /// shift the children's velocities so their total momentum equals the parent's,
+        /// assuming the parent's mass is shared equally between the children
+        #[serde(default)]
+        conserve: bool,
+    },
+}
+
+impl AsteroidSplitVelocity {
+    fn default_parent_factor() -> f32 {
+        1.0
+    }
+}
+
 #[derive(Deserialize, Debug, Reflect, Clone, Copy)]
 pub enum AsteroidSpeedRange {
     None,
@@ -27,7 +27,7 @@ pub trait AsteroidSplitSelectionExt {
         &self,
         rand: &mut RngComponent,
         pool_collection: &'a AsteroidPoolCollection,
-    ) -> Option<&'a AsteroidPool>;
+    ) -> Option<(&'a String, &'a AsteroidPool)>;
 }
 
 impl<C: AsRef<[AsteroidSelection]>> AsteroidSplitSelectionExt for C {
@@ -35,7 +35,7 @@ impl<C: AsRef<[AsteroidSelection]>> AsteroidSplitSelectionExt for C {
         &self,
         rand: &mut RngComponent,
         pool_collection: &'a AsteroidPoolCollection,
-    ) -> Option<&'a AsteroidPool> {
+    ) -> Option<(&'a String, &'a AsteroidPool)> {
         let slice = self.as_ref();
 
         let weight_sum = slice
@@ -60,7 +60,7 @@ impl<C: AsRef<[AsteroidSelection]>> AsteroidSplitSelectionExt for C {
         };
 
         match selection {
-            AsteroidSelection::Pool { key: name, .. } => pool_collection.get(name),
+            AsteroidSelection::Pool { key: name, .. } => pool_collection.get_key_value(name),
         }
     }
 }
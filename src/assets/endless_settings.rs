@@ -0,0 +1,173 @@
+use bevy::prelude::*;
+use serde::Deserialize;
+
+use crate::assets::{
+    game_assets::GameAssets, optional, AsteroidEntry, AsteroidFormation, AsteroidPhysics,
+    AsteroidSelection, AsteroidSpeedRampSettings, AsteroidSplitCount, EnemyAppearance,
+    GameLevelSettings, GameSettings, LevelAsteroidStartupSettings, LevelObjective,
+    LevelStartupSettings,
+};
+
+/// Resource is initialized after loading assets, through its [FromWorld] implementation.
+///
+/// The waves of the endless mode are generated from these settings, see
+/// [EndlessSettings::level_settings].
+#[derive(Resource, Reflect, Deserialize, Debug, Clone)]
+pub struct EndlessSettings {
+    /// backgrounds of the waves, used in turn
+    pub backgrounds: Vec<String>,
+    #[serde(default)]
+    pub asteroid_physics: AsteroidPhysics,
+    /// how the asteroids of a wave appear, after the first wave
+    #[serde(default)]
+    pub asteroid_entry: AsteroidEntry,
+    /// The difficulty curve, ordered by wave.
+    ///
+    /// Waves in between two entries are interpolated, waves after the last entry keep getting
+    /// harder at the pace of the last two entries.
+    pub difficulty: Vec<WaveDifficulty>,
+    /// upper limit for the number of asteroids of a wave
+    pub max_asteroids: usize,
+    /// upper limit for the speed factor of the asteroids of a wave
+    pub max_asteroid_speed: f32,
+    /// speeds up the asteroids while a wave is played
+    #[serde(with = "optional", skip_serializing_if = "Option::is_none", default)]
+    pub speed_ramp: Option<AsteroidSpeedRampSettings>,
+}
+
+#[derive(Reflect, Deserialize, Debug, Clone)]
+pub struct WaveDifficulty {
+    /// first wave, starting at 1, with this difficulty
+    pub wave: usize,
+    /// number of asteroids at the start of the wave
+    pub asteroids: usize,
+    /// factor applied to the speed of the asteroids
+    pub asteroid_speed: f32,
+    /// pools the asteroids are picked from, their weights are interpolated between the entries
+    pub select_from: Vec<AsteroidSelection>,
+    /// enemies appearing during this wave, and the waves up to the next entry
+    #[serde(default)]
+    pub enemies: Vec<EnemyAppearance>,
+}
+
+impl EndlessSettings {
+    /// Generates the settings of a wave, starting at 1, from the difficulty curve.
+    pub fn level_settings(&self, wave: usize) -> GameLevelSettings {
+        let (from, to) = {
+            let difficulty = &self.difficulty;
+            match difficulty.iter().position(|entry| entry.wave > wave) {
+                Some(0) => (&difficulty[0], &difficulty[0]),
+                Some(next) => (&difficulty[next - 1], &difficulty[next]),
+                None if difficulty.len() > 1 => (
+                    &difficulty[difficulty.len() - 2],
+                    &difficulty[difficulty.len() - 1],
+                ),
+                None => {
+                    let Some(last) = difficulty.last() else {
+                        error!("Settings are flawed: No difficulty specified for endless mode");
+                        panic!();
+                    };
+                    (last, last)
+                }
+            }
+        };
+        let t = if to.wave > from.wave {
+            (wave as f32 - from.wave as f32) / (to.wave - from.wave) as f32
+        } else {
+            0.0
+        };
+
+        let asteroids = lerp(from.asteroids as f32, to.asteroids as f32, t)
+            .round()
+            .clamp(1.0, self.max_asteroids.max(1) as f32) as usize;
+        let asteroid_speed =
+            lerp(from.asteroid_speed, to.asteroid_speed, t).min(self.max_asteroid_speed);
+
+        // pools only known to one of the entries fade in or out
+        let pool_weight = |entry: &WaveDifficulty, key: &str| {
+            entry
+                .select_from
+                .iter()
+                .find_map(|AsteroidSelection::Pool { key: k, weight }| {
+                    (k == key).then_some(*weight)
+                })
+                .unwrap_or(0.0)
+        };
+        let mut select_from: Vec<AsteroidSelection> = vec![];
+        for AsteroidSelection::Pool { key, .. } in from.select_from.iter().chain(&to.select_from) {
+            if select_from
+                .iter()
+                .any(|AsteroidSelection::Pool { key: k, .. }| k == key)
+            {
+                continue;
+            }
+            let weight = lerp(
+                pool_weight(from, key),
+                pool_weight(to, key),
+                t.clamp(0.0, 1.0),
+            );
+            if weight > 0.0 {
+                select_from.push(AsteroidSelection::Pool {
+                    key: key.clone(),
+                    weight,
+                });
+            }
+        }
+
+        let enemies = if wave >= to.wave {
+            to.enemies.clone()
+        } else {
+            from.enemies.clone()
+        };
+        let background = self
+            .backgrounds
+            .get((wave.max(1) - 1) % self.backgrounds.len().max(1))
+            .cloned()
+            .unwrap_or_default();
+
+        debug!(wave, asteroids, asteroid_speed, "generated endless wave");
+        GameLevelSettings {
+            background,
+            startup: LevelStartupSettings {
+                asteroids: LevelAsteroidStartupSettings {
+                    count: AsteroidSplitCount::Exact(asteroids),
+                    select_from,
+                    // the first wave starts with the player in the middle of an empty field
+                    entry: if wave > 1 {
+                        self.asteroid_entry
+                    } else {
+                        AsteroidEntry::Inside
+                    },
+                    formation: AsteroidFormation::default(),
+                },
+            },
+            next_level: None,
+            player: None,
+            asteroid_physics: self.asteroid_physics,
+            enemies,
+            asteroid_speed,
+            par_time: None,
+            time_limit: None,
+            objective: LevelObjective::ClearAll,
+            timeline: vec![],
+            speed_ramp: self.speed_ramp,
+        }
+    }
+}
+
+fn lerp(from: f32, to: f32, t: f32) -> f32 {
+    from + (to - from) * t
+}
+
+impl FromWorld for EndlessSettings {
+    fn from_world(world: &mut World) -> Self {
+        let game_settings = {
+            let assets = world.resource::<GameAssets>();
+            world
+                .resource::<Assets<GameSettings>>()
+                .get(assets.game_settings.id())
+                .unwrap()
+        };
+        game_settings.endless.clone()
+    }
+}
@@ -0,0 +1,85 @@
+use core::time::Duration;
+use std::collections::BTreeMap;
+
+use bevy::prelude::*;
+use derive_more::{Deref, DerefMut};
+use serde::Deserialize;
+
+use crate::assets::{game_assets::GameAssets, optional, SteeringSettings};
+
+/// Loaded as part of the [crate::assets::GameAssets] collection, then inserted as a resource.
+#[derive(Asset, Resource, Reflect, Deserialize, Debug, Clone, Deref, DerefMut)]
+pub struct EnemyCollection(BTreeMap<String, EnemyInfo>);
+
+impl FromWorld for EnemyCollection {
+    fn from_world(world: &mut World) -> Self {
+        let assets = world.resource::<GameAssets>();
+        world
+            .resource::<Assets<EnemyCollection>>()
+            .get(assets.enemy_settings.id())
+            .unwrap()
+            .clone()
+    }
+}
+
+#[derive(Reflect, Deserialize, Debug, Clone)]
+pub struct EnemyInfo {
+    /// key of the sprite sheet in the [crate::assets::EnemyTextureCollection]
+    pub texture_key: String,
+    pub movement: EnemyMovement,
+    #[serde(with = "optional", skip_serializing_if = "Option::is_none", default)]
+    pub weapon: Option<EnemyWeapon>,
+    /// hits needed to destroy the enemy, when hit by ammonition doing a single point of damage
+    #[serde(default = "EnemyInfo::default_hit_points")]
+    pub hit_points: u32,
+    /// points shared by the players destroying the enemy
+    pub score: usize,
+    /// behavior when the enemy is destroyed
+    #[serde(default)]
+    pub hit_behavior: Vec<EnemyHitBehavior>,
+}
+
+impl EnemyInfo {
+    fn default_hit_points() -> u32 {
+        1
+    }
+}
+
+#[derive(Reflect, Deserialize, Debug, Clone)]
+pub enum EnemyMovement {
+    /// Fly in a straight line, in a random direction
+    Drift { speed: f32 },
+    /// Fly towards the closest player, turning at most `turn_rate` radians per second
+    Hunt { speed: f32, turn_rate: f32 },
+    /// Move according to steering behaviors, starting at their maximum speed
+    Steer(SteeringSettings),
+}
+
+#[derive(Reflect, Deserialize, Debug, Clone)]
+pub struct EnemyWeapon {
+    /// key of the weapon in the [crate::assets::WeaponCollection]
+    pub weapon_key: String,
+    /// time between shots
+    pub interval: Duration,
+    #[serde(default)]
+    pub aim: EnemyAim,
+    /// whether the shots destroy asteroids as well as players
+    #[serde(default)]
+    pub hits_asteroids: bool,
+}
+
+#[derive(Reflect, Deserialize, Debug, Clone, Copy, Default)]
+pub enum EnemyAim {
+    /// Fire in the direction the enemy is facing
+    #[default]
+    Forward,
+    /// Fire at the closest player
+    Player,
+}
+
+#[derive(Reflect, Deserialize, Debug, Clone)]
+pub enum EnemyHitBehavior {
+    None,
+    Audio(String),
+    Despawn,
+}
@@ -0,0 +1,23 @@
+use std::collections::BTreeMap;
+
+use bevy::prelude::*;
+use derive_more::{Constructor, Deref, DerefMut};
+
+use crate::assets::{game_assets::GameAssets, SpriteSheetAsset};
+
+/// Loaded as part of the [crate::assets::GameAssets] collection, then inserted as a [Resource].
+#[derive(Asset, Resource, Reflect, Debug, Clone, Deref, DerefMut, Constructor)]
+pub struct EnemyTextureCollection(BTreeMap<String, SpriteSheetAsset>);
+
+impl FromWorld for EnemyTextureCollection {
+    fn from_world(world: &mut World) -> Self {
+        let assets = world.resource::<GameAssets>();
+        let asset = world
+            .resource::<Assets<EnemyTextureCollection>>()
+            .get(assets.enemy_texture_collection_handle.id())
+            .unwrap()
+            .clone();
+        debug!(enemy_sprites = asset.len());
+        asset
+    }
+}
@@ -5,7 +5,8 @@ use crate::assets::{GameSettings, SpriteSheetAsset};
 
 use crate::assets::{
     AmmonitionDepot, AmmonitionTextureCollection, AsteroidPoolCollection,
-    AsteroidTextureCollection, GameLevelSettingsCollection, InputKeySettings, WeaponCollection,
+    AsteroidTextureCollection, EnemyCollection, EnemyTextureCollection,
+    GameLevelSettingsCollection, InputKeySettings, PlayerTextureCollection, WeaponCollection,
 };
 
 /// Loaded directly as a [Resource] by [bevy_asset_loader].
@@ -34,12 +35,13 @@ pub struct GameAssets {
     #[asset(key = "asteroid-pool-collection")]
     pub asteroid_pool_settings: Handle<AsteroidPoolCollection>,
 
+    /// The content of this asset can be found as a [Resource].
+    #[asset(key = "enemy-collection")]
+    pub enemy_settings: Handle<EnemyCollection>,
+
     /****************
      * spritesheets *
      ****************/
-    #[asset(key = "player-sheet")]
-    pub player_spritesheet_handle: Handle<SpriteSheetAsset>,
-
     #[asset(key = "flames-sheet")]
     pub flames_spritesheet_handle: Handle<SpriteSheetAsset>,
 
@@ -50,20 +52,14 @@ pub struct GameAssets {
     /// The content of this asset can be found as a [Resource].
     #[asset(key = "asteroid-texture-collection")]
     pub asteroid_texture_collection_handle: Handle<AsteroidTextureCollection>,
-}
 
-#[derive(SystemParam)]
-pub struct PlayerSpriteSheet<'w> {
-    game_assets: Res<'w, GameAssets>,
-    spritesheet_assets: Res<'w, Assets<SpriteSheetAsset>>,
-}
+    /// The content of this asset can be found as a [Resource].
+    #[asset(key = "enemy-texture-collection")]
+    pub enemy_texture_collection_handle: Handle<EnemyTextureCollection>,
 
-impl PlayerSpriteSheet<'_> {
-    pub fn spritesheet(&self) -> &SpriteSheetAsset {
-        self.spritesheet_assets
-            .get(self.game_assets.player_spritesheet_handle.id())
-            .unwrap()
-    }
+    /// The content of this asset can be found as a [Resource].
+    #[asset(key = "player-texture-collection")]
+    pub player_texture_collection_handle: Handle<PlayerTextureCollection>,
 }
 
 #[derive(SystemParam)]
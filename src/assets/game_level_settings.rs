@@ -1,8 +1,11 @@
+use core::time::Duration;
+
 use bevy::{prelude::*, utils::HashMap};
 use serde::Deserialize;
 
 use crate::assets::{
-    game_assets::GameAssets, optional, LevelStartupSettings, PlayerSettingOptions,
+    game_assets::GameAssets, optional, AsteroidEntry, AsteroidFormation, AsteroidSelection,
+    AsteroidSplitCount, DifficultyPreset, LevelStartupSettings, PlayerSettingOptions,
 };
 
 /// Resource is initialized after loading assets, through its [FromWorld] implementation.
@@ -31,4 +34,168 @@ pub struct GameLevelSettings {
     pub next_level: Option<String>,
     #[serde(with = "optional", skip_serializing_if = "Option::is_none", default)]
     pub player: Option<PlayerSettingOptions>,
+    #[serde(default)]
+    pub asteroid_physics: AsteroidPhysics,
+    /// enemies appearing while the level is played
+    #[serde(default)]
+    pub enemies: Vec<EnemyAppearance>,
+    /// factor applied to the speed of all asteroids of the level
+    #[serde(default = "GameLevelSettings::default_asteroid_speed")]
+    pub asteroid_speed: f32,
+    /// clearing the level faster earns a time bonus, see
+    /// [crate::assets::GameStartSettings::time_bonus_per_second]
+    #[serde(with = "optional", skip_serializing_if = "Option::is_none", default)]
+    pub par_time: Option<Duration>,
+    /// running out of time ends the game in [crate::assets::GameMode::TimeAttack]
+    #[serde(with = "optional", skip_serializing_if = "Option::is_none", default)]
+    pub time_limit: Option<Duration>,
+    /// what has to be done to clear the level
+    #[serde(default)]
+    pub objective: LevelObjective,
+    /// events happening while the level is played, see [crate::director]
+    #[serde(default)]
+    pub timeline: Vec<TimelineEvent>,
+    /// speeds up the asteroids while the level is played
+    #[serde(with = "optional", skip_serializing_if = "Option::is_none", default)]
+    pub speed_ramp: Option<AsteroidSpeedRampSettings>,
+}
+
+impl GameLevelSettings {
+    fn default_asteroid_speed() -> f32 {
+        1.0
+    }
+
+    /// Speeds up the asteroids, and changes their number at the start and in the timeline.
+    pub fn apply_difficulty(mut self, difficulty: &DifficultyPreset) -> Self {
+        self.asteroid_speed = difficulty.asteroid_speed.apply(self.asteroid_speed);
+
+        let asteroid_count = difficulty.asteroid_count;
+        let count = &mut self.startup.asteroids.count;
+        *count = match *count {
+            AsteroidSplitCount::Exact(count) => {
+                AsteroidSplitCount::Exact(asteroid_count.apply_count(count))
+            }
+            AsteroidSplitCount::Range { start, end } => {
+                let start = asteroid_count.apply_count(start);
+                // the range must not become empty
+                let end = asteroid_count.apply_count(end).max(start + 1);
+                AsteroidSplitCount::Range { start, end }
+            }
+        };
+        for event in self.timeline.iter_mut() {
+            for action in event.actions.iter_mut() {
+                if let TimelineAction::SpawnAsteroids { count, .. } = action {
+                    *count = asteroid_count.apply_count(*count);
+                }
+            }
+        }
+        self
+    }
+}
+
+#[derive(Deserialize, Reflect, Debug, Clone)]
+pub struct EnemyAppearance {
+    /// key of the enemy in the [crate::assets::EnemyCollection]
+    pub enemy: String,
+    /// time spent playing the level before the enemy appears
+    pub delay: Duration,
+    /// the enemy keeps appearing with this interval, if given
+    #[serde(with = "optional", skip_serializing_if = "Option::is_none", default)]
+    pub repeat: Option<Duration>,
+}
+
+#[derive(Deserialize, Reflect, Debug, Clone)]
+pub struct TimelineEvent {
+    pub when: TimelineTrigger,
+    pub actions: Vec<TimelineAction>,
+    /// the event can happen again after this time, if given
+    #[serde(with = "optional", skip_serializing_if = "Option::is_none", default)]
+    pub repeat: Option<Duration>,
+}
+
+/// The condition for a [TimelineEvent] to happen.
+#[derive(Deserialize, Reflect, Debug, Clone)]
+pub enum TimelineTrigger {
+    /// time spent playing the level
+    At(Duration),
+    /// fewer asteroids than the given number are left
+    AsteroidsBelow(usize),
+}
+
+#[derive(Deserialize, Reflect, Debug, Clone)]
+pub enum TimelineAction {
+    /// asteroids picked from the given pools
+    SpawnAsteroids {
+        count: usize,
+        select_from: Vec<AsteroidSelection>,
+        #[serde(default)]
+        entry: AsteroidEntry,
+        #[serde(default)]
+        formation: AsteroidFormation,
+    },
+    /// key of the enemy in the [crate::assets::EnemyCollection]
+    SpawnEnemy(String),
+    /// path of the audio asset to play
+    PlayAudio(String),
+}
+
+/// A multiplier for the velocity of all asteroids, growing from 1 with the time played and with
+/// the asteroids destroyed, see [crate::asteroid::AsteroidSpeedRamp].
+#[derive(Deserialize, Reflect, Debug, Clone, Copy)]
+pub struct AsteroidSpeedRampSettings {
+    /// added to the multiplier for every second of the level
+    #[serde(default)]
+    pub per_second: f32,
+    /// below this number of asteroids left, the multiplier grows with every one missing
+    #[serde(default)]
+    pub asteroids_below: usize,
+    /// added to the multiplier for every asteroid missing to `asteroids_below`
+    #[serde(default)]
+    pub per_missing_asteroid: f32,
+    /// the multiplier doesn't grow beyond this
+    pub max: f32,
+}
+
+impl AsteroidSpeedRampSettings {
+    pub fn multiplier(&self, elapsed: Duration, asteroids_left: usize) -> f32 {
+        let missing = self.asteroids_below.saturating_sub(asteroids_left) as f32;
+        (1.0 + self.per_second * elapsed.as_secs_f32() + self.per_missing_asteroid * missing)
+            .min(self.max)
+    }
+}
+
+/// The win condition of a level, see [crate::levels::ObjectiveProgress].
+#[derive(Deserialize, Reflect, Debug, Clone, Default, PartialEq)]
+pub enum LevelObjective {
+    /// Destroy all asteroids
+    #[default]
+    ClearAll,
+    /// Stay in the game for the given time
+    Survive(Duration),
+    /// Score the given number of points during the level, all players combined
+    Score(usize),
+    /// Destroy the given number of asteroids spawned from a pool of the
+    /// [crate::assets::AsteroidPoolCollection]
+    DestroyFromPool { pool: String, count: usize },
+    /// Keep an escort ship in one piece for the given time
+    Escort {
+        /// key of the ship in the [crate::assets::PlayerTextureCollection]
+        texture: String,
+        hit_points: u32,
+        position: Vec2,
+        /// the escort drifts across the playing field at this speed
+        speed: f32,
+        duration: Duration,
+    },
+}
+
+/// How asteroids are moved by the physics engine.
+#[derive(Deserialize, Reflect, Debug, Clone, Copy, Default, PartialEq)]
+pub enum AsteroidPhysics {
+    /// Asteroids keep their velocity and pass through each other
+    #[default]
+    Kinematic,
+    /// Asteroids bounce off each other, using the density, friction and restitution of their
+    /// colliders
+    Dynamic,
 }
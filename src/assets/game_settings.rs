@@ -2,7 +2,7 @@ use bevy::prelude::*;
 use derive_more::Constructor;
 use serde::Deserialize;
 
-use crate::assets::{DefaultLevelSettings, GameAreaSettings, GameStartSettings};
+use crate::assets::{DefaultLevelSettings, EndlessSettings, GameAreaSettings, GameStartSettings};
 
 /// Loaded as part of the [crate::assets::GameAssets] collection.
 #[derive(Asset, Reflect, Deserialize, Debug, Clone, Constructor)]
@@ -10,4 +10,5 @@ pub struct GameSettings {
     pub game_area: GameAreaSettings,
     pub game_start: GameStartSettings,
     pub level_defaults: DefaultLevelSettings,
+    pub endless: EndlessSettings,
 }
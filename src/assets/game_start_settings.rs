@@ -1,9 +1,11 @@
 use core::time::Duration;
 
 use bevy::prelude::*;
-use serde::Deserialize;
+use derive_more::derive::Display;
+use serde::{Deserialize, Serialize};
+use strum_macros::EnumIter;
 
-use crate::assets::{game_assets::GameAssets, GameSettings};
+use crate::assets::{game_assets::GameAssets, optional, GameSettings};
 
 /// Resource is initialized after loading assets, through its [FromWorld] implementation.
 #[derive(Resource, Reflect, Deserialize, Debug, Clone)]
@@ -14,6 +16,188 @@ pub struct GameStartSettings {
     pub countdown_duration: Duration,
     pub minimum_countdown_duration: Duration,
     pub new_life_every: usize,
+    /// number of local players, each needs a section in the [crate::assets::InputKeySettings]
+    #[serde(default = "GameStartSettings::default_players")]
+    pub players: usize,
+    /// keys of the ships in the [crate::assets::PlayerTextureCollection], in player order
+    pub player_textures: Vec<String>,
+    /// chosen in the main menu
+    #[serde(default)]
+    pub mode: GameMode,
+    /// rules of a [GameMode::Versus] match
+    pub versus: VersusSettings,
+    /// points awarded for every second left of the
+    /// [crate::assets::GameLevelSettings::par_time] when a level is cleared
+    #[serde(default)]
+    pub time_bonus_per_second: usize,
+    /// chosen in the main menu, or with `--difficulty`
+    #[serde(default)]
+    pub difficulty: Difficulty,
+    /// how each [Difficulty] changes the settings of a game
+    #[serde(default)]
+    pub difficulties: DifficultySettings,
+}
+
+#[derive(Reflect, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq, EnumIter)]
+#[cfg_attr(feature = "cmd_line", derive(clap::ValueEnum))]
+pub enum GameMode {
+    /// Players fight their way through the levels together
+    #[default]
+    Campaign,
+    /// Players shoot at each other, with asteroids as hazards in between
+    Versus,
+    /// Players survive generated waves of increasing difficulty, see
+    /// [crate::assets::EndlessSettings]
+    Endless,
+    /// Players race through the levels, running out of the
+    /// [crate::assets::GameLevelSettings::time_limit] ends the game
+    TimeAttack,
+}
+
+#[derive(
+    Reflect,
+    Deserialize,
+    Serialize,
+    Display,
+    Debug,
+    Clone,
+    Copy,
+    Default,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    EnumIter,
+)]
+#[cfg_attr(feature = "cmd_line", derive(clap::ValueEnum))]
+pub enum Difficulty {
+    Easy,
+    #[default]
+    Normal,
+    Hard,
+    Insane,
+}
+
+impl Difficulty {
+    /// The next harder difficulty, after [Difficulty::Insane] it starts over with the easiest.
+    pub fn next(self) -> Self {
+        match self {
+            Difficulty::Easy => Difficulty::Normal,
+            Difficulty::Normal => Difficulty::Hard,
+            Difficulty::Hard => Difficulty::Insane,
+            Difficulty::Insane => Difficulty::Easy,
+        }
+    }
+}
+
+/// The [DifficultyPreset] of each [Difficulty], left out presets keep the settings as they are.
+#[derive(Reflect, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct DifficultySettings {
+    pub easy: DifficultyPreset,
+    pub normal: DifficultyPreset,
+    pub hard: DifficultyPreset,
+    pub insane: DifficultyPreset,
+}
+
+impl DifficultySettings {
+    pub fn preset(&self, difficulty: Difficulty) -> &DifficultyPreset {
+        match difficulty {
+            Difficulty::Easy => &self.easy,
+            Difficulty::Normal => &self.normal,
+            Difficulty::Hard => &self.hard,
+            Difficulty::Insane => &self.insane,
+        }
+    }
+}
+
+/// Changes to the game and level settings, applied in [crate::levels::init_level_settings] and
+/// when the players start a game.
+#[derive(Reflect, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct DifficultyPreset {
+    /// lives of each player at the start of a game
+    pub lives: SettingAdjustment,
+    /// score needed for an additional life
+    pub new_life_every: SettingAdjustment,
+    /// the [crate::assets::GameLevelSettings::asteroid_speed], speeding up or slowing down the
+    /// asteroids of all pools
+    pub asteroid_speed: SettingAdjustment,
+    /// asteroids at the start of a level, and in the waves of its timeline
+    pub asteroid_count: SettingAdjustment,
+    pub player_acceleration: SettingAdjustment,
+    pub safe_radius: SettingAdjustment,
+}
+
+/// How a [DifficultyPreset] changes a single setting.
+#[derive(Reflect, Deserialize, Debug, Clone, Copy, Default, PartialEq)]
+pub enum SettingAdjustment {
+    #[default]
+    Keep,
+    /// Multiplies the setting
+    Factor(f32),
+    /// Replaces the setting
+    Override(f32),
+}
+
+impl SettingAdjustment {
+    pub fn apply(self, value: f32) -> f32 {
+        match self {
+            SettingAdjustment::Keep => value,
+            SettingAdjustment::Factor(factor) => value * factor,
+            SettingAdjustment::Override(value) => value,
+        }
+    }
+
+    /// Adjusts a count, rounded to the nearest whole number.
+    pub fn apply_count(self, count: usize) -> usize {
+        self.apply(count as f32).round().max(0.0) as usize
+    }
+}
+
+#[derive(Reflect, Deserialize, Debug, Clone)]
+pub struct VersusSettings {
+    /// level providing the asteroid pools and the enemies of the arena
+    pub level: String,
+    /// points awarded for shooting down another player
+    pub kill_points: usize,
+    /// the first player reaching this number of kills wins the match
+    #[serde(with = "optional", default)]
+    pub kill_limit: Option<usize>,
+    /// the first player reaching this score wins the match
+    #[serde(with = "optional", default)]
+    pub score_limit: Option<usize>,
+}
+
+impl GameStartSettings {
+    /// Local players are limited by the available keyboard sections and ship textures.
+    pub const MAX_PLAYERS: usize = 4;
+
+    fn default_players() -> usize {
+        1
+    }
+
+    /// The preset of the chosen [Difficulty].
+    pub fn difficulty_preset(&self) -> &DifficultyPreset {
+        self.difficulties.preset(self.difficulty)
+    }
+
+    /// Lives of each player at the start of a game, adjusted to the [Difficulty].
+    pub fn starting_lives(&self) -> usize {
+        self.difficulty_preset()
+            .lives
+            .apply_count(self.lives)
+            .max(1)
+    }
+
+    /// Score needed for an additional life, adjusted to the [Difficulty].
+    pub fn score_per_life(&self) -> usize {
+        self.difficulty_preset()
+            .new_life_every
+            .apply_count(self.new_life_every)
+            .max(1)
+    }
 }
 
 impl FromWorld for GameStartSettings {
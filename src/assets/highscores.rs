@@ -5,27 +5,35 @@ use derive_more::derive::Display;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-use crate::player::Score;
+use crate::{assets::Difficulty, player::Score};
 
 #[derive(Resource, Debug, Clone, Serialize, Deserialize, Default)]
 pub struct HighScoreBoard {
     scores: Vec<HighScore>,
 }
 
+/// The highscores of the endless mode, kept apart from those of the campaign.
+#[derive(Resource, Debug, Clone, Serialize, Deserialize, Default, Deref, DerefMut)]
+pub struct EndlessHighScoreBoard(HighScoreBoard);
+
 #[derive(Debug, Display, Clone, PartialEq, PartialOrd, Eq, Ord, Serialize, Deserialize)]
 #[display("{name}: {score}")]
 pub struct HighScore {
     score: Score,
     name: String,
     datetime: std::time::SystemTime,
+    /// scores saved before difficulties were introduced count as [Difficulty::Normal]
+    #[serde(default)]
+    difficulty: Difficulty,
 }
 
 impl HighScore {
-    pub fn new(name: impl Into<String>, score: Score) -> Self {
+    pub fn new(name: impl Into<String>, score: Score, difficulty: Difficulty) -> Self {
         Self {
             name: name.into(),
             score,
             datetime: std::time::SystemTime::now(),
+            difficulty,
         }
     }
 
@@ -40,6 +48,10 @@ impl HighScore {
     pub fn datetime(&self) -> std::time::SystemTime {
         self.datetime
     }
+
+    pub fn difficulty(&self) -> Difficulty {
+        self.difficulty
+    }
 }
 
 #[derive(Resource, Debug, Eq)]
@@ -79,6 +91,7 @@ impl HighScoreBoard {
     pub fn add_score(
         &mut self,
         score: Score,
+        difficulty: Difficulty,
         old_key: Option<&HighScoreKey>,
     ) -> Option<HighScoreKey> {
         let mut place_map = SCORE_INDEX_KEYS.lock().unwrap();
@@ -99,7 +112,7 @@ impl HighScoreBoard {
 
             let key = Uuid::now_v7();
             self.scores
-                .insert(place, HighScore::new(DEFAULT_USER, score));
+                .insert(place, HighScore::new(DEFAULT_USER, score, difficulty));
             place_map.insert(key, place);
 
             while self.scores.len() > NUM_HIGH_SCORES {
@@ -117,7 +130,8 @@ impl HighScoreBoard {
             debug!(?score, ?place, "found new highscore");
 
             let key = Uuid::now_v7();
-            self.scores.push(HighScore::new(DEFAULT_USER, score));
+            self.scores
+                .push(HighScore::new(DEFAULT_USER, score, difficulty));
             place_map.insert(key, place);
 
             return Some(HighScoreKey {
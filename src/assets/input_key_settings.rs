@@ -3,19 +3,36 @@ use core::time::Duration;
 use bevy::prelude::*;
 use serde::{Deserialize, Serialize};
 
-use crate::assets::game_assets::GameAssets;
-//use crate::assets::optional;
+use crate::assets::{game_assets::GameAssets, optional};
 
 /// Loaded directly as part of the [crate::assets::GameAssets] collection, then inserted as a [Resource].
 #[derive(Asset, Resource, Deserialize, Serialize, Reflect, Debug, Clone)]
 pub struct InputKeySettings {
     pub auto_fire: Duration,
+    pub pause: KeyCode,
+    /// one section per local player, in player order
+    pub players: Vec<PlayerKeySettings>,
+}
+
+#[derive(Deserialize, Serialize, Reflect, Debug, Clone)]
+pub struct PlayerKeySettings {
     pub rotate_left: KeyCode,
     pub rotate_right: KeyCode,
     pub accelerate: KeyCode,
     pub fire: KeyCode,
     pub jump: KeyCode,
-    pub pause: KeyCode,
+    pub shield: KeyCode,
+    /// moves the reticle of a targeted jump
+    #[serde(with = "optional", skip_serializing_if = "Option::is_none", default)]
+    pub aim: Option<AimKeySettings>,
+}
+
+#[derive(Deserialize, Serialize, Reflect, Debug, Clone)]
+pub struct AimKeySettings {
+    pub up: KeyCode,
+    pub down: KeyCode,
+    pub left: KeyCode,
+    pub right: KeyCode,
 }
 
 impl FromWorld for InputKeySettings {
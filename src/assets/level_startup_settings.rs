@@ -1,3 +1,5 @@
+use core::time::Duration;
+
 use bevy::prelude::*;
 use serde::Deserialize;
 
@@ -12,4 +14,104 @@ pub struct LevelStartupSettings {
 pub struct LevelAsteroidStartupSettings {
     pub count: AsteroidSplitCount,
     pub select_from: Vec<AsteroidSelection>,
+    #[serde(default)]
+    pub entry: AsteroidEntry,
+    /// Layout of the asteroids entering [AsteroidEntry::Inside]
+    #[serde(default)]
+    pub formation: AsteroidFormation,
+}
+
+/// Where new asteroids appear in the game area.
+#[derive(Deserialize, Reflect, Debug, Clone, Copy, Default, PartialEq)]
+pub enum AsteroidEntry {
+    /// At a random position inside the game area, outside the safe radius of the players
+    #[default]
+    Inside,
+    /// Flying in from outside an edge of the game area, announced by a blinking warning at the
+    /// edge for the given time
+    Edges { warning: Duration },
+}
+
+/// Hand-designed arrangement of asteroids, positions are relative to the middle of the game area.
+#[derive(Deserialize, Reflect, Debug, Clone, Default, PartialEq)]
+#[serde(default)]
+pub struct AsteroidFormation {
+    pub layout: AsteroidLayout,
+    /// Maximum random offset of each asteroid from its place in the layout
+    pub jitter: f32,
+    pub velocity: AsteroidVelocityPattern,
+}
+
+#[derive(Deserialize, Reflect, Debug, Clone, Default, PartialEq)]
+pub enum AsteroidLayout {
+    /// At random positions outside the safe radius of the players
+    #[default]
+    Random,
+    /// Evenly spread on a circle
+    Ring {
+        #[serde(default)]
+        center: Vec2,
+        radius: f32,
+    },
+    /// Evenly spread from one point to the other
+    Line { from: Vec2, to: Vec2 },
+    /// Row by row, starting at the top left
+    Grid {
+        #[serde(default)]
+        center: Vec2,
+        columns: usize,
+        spacing: Vec2,
+    },
+    /// Winding outwards from the inner to the outer radius
+    Spiral {
+        #[serde(default)]
+        center: Vec2,
+        #[serde(default)]
+        inner_radius: f32,
+        outer_radius: f32,
+        turns: f32,
+    },
+    /// At random positions within the radius
+    Cluster {
+        #[serde(default)]
+        center: Vec2,
+        radius: f32,
+    },
+    /// At the given positions, asteroids beyond the number of points are left out
+    Points(Vec<Vec2>),
+}
+
+impl AsteroidLayout {
+    /// The middle of the formation, the reference for the [AsteroidVelocityPattern].
+    pub fn center(&self) -> Vec2 {
+        match self {
+            AsteroidLayout::Random => Vec2::ZERO,
+            AsteroidLayout::Ring { center, .. }
+            | AsteroidLayout::Grid { center, .. }
+            | AsteroidLayout::Spiral { center, .. }
+            | AsteroidLayout::Cluster { center, .. } => *center,
+            AsteroidLayout::Line { from, to } => (*from + *to) / 2.0,
+            AsteroidLayout::Points(points) if points.is_empty() => Vec2::ZERO,
+            AsteroidLayout::Points(points) => points.iter().sum::<Vec2>() / points.len() as f32,
+        }
+    }
+}
+
+/// Initial direction of the asteroids in a formation, the speed is given by their pool.
+#[derive(Deserialize, Reflect, Debug, Clone, Copy, Default, PartialEq)]
+pub enum AsteroidVelocityPattern {
+    /// Each asteroid in a random direction
+    #[default]
+    Random,
+    /// Circling around the center of the formation
+    Orbit {
+        #[serde(default)]
+        clockwise: bool,
+    },
+    /// Heading for the center of the formation
+    Converge,
+    /// Flying away from the center of the formation
+    Diverge,
+    /// All asteroids in the same direction
+    Heading(Vec2),
 }
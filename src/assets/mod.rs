@@ -4,6 +4,9 @@ mod asteroid_pool_collection;
 mod asteroid_selection;
 mod asteroid_texture_collection;
 mod default_level_settings;
+mod endless_settings;
+mod enemy_collection;
+mod enemy_texture_collection;
 mod game_area_settings;
 mod game_level_settings;
 mod game_settings;
@@ -13,10 +16,12 @@ mod input_key_settings;
 mod level_startup_settings;
 mod optional; // for deserialization and serialization of Option<T>
 mod player_settings;
+mod player_texture_collection;
 mod plugin;
 mod sprite_dynamic_asset_collection;
 mod sprite_sheet_asset;
 mod state_backgrounds;
+mod steering_settings;
 mod weapon_collection;
 
 pub mod game_assets;
@@ -24,8 +29,9 @@ pub mod game_assets;
 pub use self::{
     ammonition_depot::*, ammonition_texture_collection::*, asteroid_pool_collection::*,
     asteroid_selection::*, asteroid_texture_collection::*, default_level_settings::*,
-    game_area_settings::*, game_level_settings::*, game_settings::*, game_start_settings::*,
-    highscores::*, input_key_settings::*, level_startup_settings::*, player_settings::*, plugin::*,
-    sprite_dynamic_asset_collection::*, sprite_sheet_asset::*, state_backgrounds::*,
-    weapon_collection::*,
+    endless_settings::*, enemy_collection::*, enemy_texture_collection::*, game_area_settings::*,
+    game_level_settings::*, game_settings::*, game_start_settings::*, highscores::*,
+    input_key_settings::*, level_startup_settings::*, player_settings::*,
+    player_texture_collection::*, plugin::*, sprite_dynamic_asset_collection::*,
+    sprite_sheet_asset::*, state_backgrounds::*, steering_settings::*, weapon_collection::*,
 };
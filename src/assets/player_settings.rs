@@ -3,7 +3,10 @@ use core::{ops::Range, time::Duration};
 use bevy::prelude::*;
 use serde::Deserialize;
 
-use crate::assets::optional;
+use crate::{
+    assets::{optional, DifficultyPreset},
+    movement::GameArea,
+};
 
 #[derive(Reflect, Deserialize, Debug, Clone)]
 pub struct PlayerSettingOptions {
@@ -26,7 +29,37 @@ pub struct PlayerSettingOptions {
     #[serde(with = "optional", skip_serializing_if = "Option::is_none", default)]
     pub minimum_jump_distance: Option<f32>,
     #[serde(with = "optional", skip_serializing_if = "Option::is_none", default)]
+    pub jump_cooldown: Option<Duration>,
+    #[serde(with = "optional", skip_serializing_if = "Option::is_none", default)]
+    pub max_jumps_per_life: Option<usize>,
+    #[serde(with = "optional", skip_serializing_if = "Option::is_none", default)]
+    pub jump_malfunction: Option<JumpMalfunction>,
+    #[serde(with = "optional", skip_serializing_if = "Option::is_none", default)]
+    pub safe_landing_radius: Option<f32>,
+    #[serde(with = "optional", skip_serializing_if = "Option::is_none", default)]
+    pub jump_mode: Option<JumpMode>,
+    #[serde(with = "optional", skip_serializing_if = "Option::is_none", default)]
+    pub flight_model: Option<FlightModel>,
+    #[serde(with = "optional", skip_serializing_if = "Option::is_none", default)]
+    pub invulnerability_duration: Option<Duration>,
+    #[serde(with = "optional", skip_serializing_if = "Option::is_none", default)]
+    pub invulnerability_blink_interval: Option<Duration>,
+    #[serde(with = "optional", skip_serializing_if = "Option::is_none", default)]
     pub flames_audio: Option<String>,
+    #[serde(with = "optional", skip_serializing_if = "Option::is_none", default)]
+    pub shield_capacity: Option<f32>,
+    #[serde(with = "optional", skip_serializing_if = "Option::is_none", default)]
+    pub shield_regeneration: Option<f32>,
+    #[serde(with = "optional", skip_serializing_if = "Option::is_none", default)]
+    pub shield_activation_cost: Option<f32>,
+    #[serde(with = "optional", skip_serializing_if = "Option::is_none", default)]
+    pub shield_drain: Option<f32>,
+    #[serde(with = "optional", skip_serializing_if = "Option::is_none", default)]
+    pub shield_hit_cost: Option<f32>,
+    #[serde(with = "optional", skip_serializing_if = "Option::is_none", default)]
+    pub shield_radius: Option<f32>,
+    #[serde(with = "optional", skip_serializing_if = "Option::is_none", default)]
+    pub shield_contact: Option<ShieldContact>,
 }
 
 /// Resource is initialized during [crate::states::init_level_settings].
@@ -41,8 +74,88 @@ pub struct PlayerSettings {
     pub rotation_speed_acceleration: f32,
     pub jump_animation_duration: Duration,
     pub minimum_jump_distance: f32,
+    /// time after a jump before the next jump is possible
+    pub jump_cooldown: Duration,
+    #[serde(with = "optional", skip_serializing_if = "Option::is_none", default)]
+    pub max_jumps_per_life: Option<usize>,
+    #[serde(with = "optional", skip_serializing_if = "Option::is_none", default)]
+    pub jump_malfunction: Option<JumpMalfunction>,
+    /// jump destinations with an asteroid or enemy within this radius are avoided, if possible
+    #[serde(default)]
+    pub safe_landing_radius: f32,
+    #[serde(default)]
+    pub jump_mode: JumpMode,
+    #[serde(default)]
+    pub flight_model: FlightModel,
+    /// time after a respawn during which collisions are ignored, asteroids within the
+    /// `safe_radius` are only cleared when this is zero
+    pub invulnerability_duration: Duration,
+    /// time the player sprite takes to fade out or in while invulnerable
+    pub invulnerability_blink_interval: Duration,
     #[serde(with = "optional", skip_serializing_if = "Option::is_none", default)]
     pub flames_audio: Option<String>,
+    /// maximum shield energy, the shield starts fully charged
+    pub shield_capacity: f32,
+    /// energy regained per second while the shield is inactive
+    pub shield_regeneration: f32,
+    /// energy needed to raise the shield
+    pub shield_activation_cost: f32,
+    /// energy used per second while the shield is active
+    pub shield_drain: f32,
+    /// energy used for every contact absorbed by the shield
+    pub shield_hit_cost: f32,
+    /// radius of the bubble deflecting everything touching it while the shield is active
+    pub shield_radius: f32,
+    pub shield_contact: ShieldContact,
+}
+
+#[derive(Reflect, Deserialize, Debug, Clone, Copy, Default, PartialEq)]
+pub enum FlightModel {
+    /// Kinematic body, `acceleration` and the turning ramp change velocity and rotation directly
+    #[default]
+    Arcade,
+    /// Dynamic body, its mass comes from the collider density. Thrust and turning are applied as
+    /// force and torque, and collisions push the ship around.
+    Newtonian {
+        thrust: f32,
+        angular_thrust: f32,
+        #[serde(default)]
+        angular_damping: f32,
+    },
+}
+
+#[derive(Reflect, Deserialize, Debug, Clone, Copy, Default)]
+pub enum JumpMode {
+    /// Jump to a random destination, as soon as jump is pressed
+    #[default]
+    Random,
+    /// Holding jump shows a reticle moved with the aim keys, releasing jump jumps there
+    Targeted { reticle_speed: f32 },
+}
+
+#[derive(Reflect, Deserialize, Debug, Clone, Copy)]
+pub struct JumpMalfunction {
+    /// probability between 0.0 and 1.0 of a jump going wrong
+    pub chance: f32,
+    pub effect: JumpMalfunctionEffect,
+}
+
+#[derive(Reflect, Deserialize, Debug, Clone, Copy)]
+pub enum JumpMalfunctionEffect {
+    /// The ship is destroyed
+    Death,
+    /// The ship lands somewhere within `max_distance`, ignoring the safe landing search
+    ShortJump { max_distance: f32 },
+}
+
+/// What happens to asteroids and enemies touching an active shield.
+#[derive(Reflect, Deserialize, Debug, Clone, Copy, Default)]
+pub enum ShieldContact {
+    /// Reflect their velocity away from the player
+    #[default]
+    Bounce,
+    /// Take the hit points off them, as if shot by the player
+    Hit { damage: u32 },
 }
 
 impl PlayerSettings {
@@ -75,10 +188,66 @@ impl PlayerSettings {
             if let Some(value) = &options.minimum_jump_distance {
                 self.minimum_jump_distance = *value;
             }
+            if let Some(value) = &options.jump_cooldown {
+                self.jump_cooldown = *value;
+            }
+            if options.max_jumps_per_life.is_some() {
+                self.max_jumps_per_life = options.max_jumps_per_life;
+            }
+            if options.jump_malfunction.is_some() {
+                self.jump_malfunction = options.jump_malfunction;
+            }
+            if let Some(value) = &options.safe_landing_radius {
+                self.safe_landing_radius = *value;
+            }
+            if let Some(value) = &options.jump_mode {
+                self.jump_mode = *value;
+            }
+            if let Some(value) = &options.flight_model {
+                self.flight_model = *value;
+            }
+            if let Some(value) = &options.invulnerability_duration {
+                self.invulnerability_duration = *value;
+            }
+            if let Some(value) = &options.invulnerability_blink_interval {
+                self.invulnerability_blink_interval = *value;
+            }
             if options.flames_audio.is_some() {
                 self.flames_audio = options.flames_audio.clone();
             }
+            if let Some(value) = &options.shield_capacity {
+                self.shield_capacity = *value;
+            }
+            if let Some(value) = &options.shield_regeneration {
+                self.shield_regeneration = *value;
+            }
+            if let Some(value) = &options.shield_activation_cost {
+                self.shield_activation_cost = *value;
+            }
+            if let Some(value) = &options.shield_drain {
+                self.shield_drain = *value;
+            }
+            if let Some(value) = &options.shield_hit_cost {
+                self.shield_hit_cost = *value;
+            }
+            if let Some(value) = &options.shield_radius {
+                self.shield_radius = *value;
+            }
+            if let Some(value) = &options.shield_contact {
+                self.shield_contact = *value;
+            }
         }
         self
     }
+
+    pub fn apply_difficulty(mut self, difficulty: &DifficultyPreset) -> Self {
+        self.acceleration = difficulty.player_acceleration.apply(self.acceleration);
+        self.safe_radius = difficulty.safe_radius.apply(self.safe_radius);
+        self
+    }
+
+    /// Whether a targeted jump from the position may land on the target.
+    pub fn is_valid_jump_target(&self, game_area: &GameArea, from: Vec2, target: Vec2) -> bool {
+        game_area.contains(target) && target.distance(from) > self.minimum_jump_distance
+    }
 }
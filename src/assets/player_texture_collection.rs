@@ -0,0 +1,23 @@
+use std::collections::BTreeMap;
+
+use bevy::prelude::*;
+use derive_more::{Constructor, Deref, DerefMut};
+
+use crate::assets::{game_assets::GameAssets, SpriteSheetAsset};
+
+/// Loaded as part of the [crate::assets::GameAssets] collection, then inserted as a [Resource].
+#[derive(Asset, Resource, Reflect, Debug, Clone, Deref, DerefMut, Constructor)]
+pub struct PlayerTextureCollection(BTreeMap<String, SpriteSheetAsset>);
+
+impl FromWorld for PlayerTextureCollection {
+    fn from_world(world: &mut World) -> Self {
+        let assets = world.resource::<GameAssets>();
+        let asset = world
+            .resource::<Assets<PlayerTextureCollection>>()
+            .get(assets.player_texture_collection_handle.id())
+            .unwrap()
+            .clone();
+        debug!(player_sprites = asset.len());
+        asset
+    }
+}
@@ -9,10 +9,12 @@ use crate::{
     assets::{
         game_assets::GameAssets, sprite_dynamic_asset_collection::SpriteDynamicAssetCollection,
         AmmonitionDepot, AmmonitionTextureCollection, AsteroidPoolCollection,
-        AsteroidTextureCollection, DefaultLevelSettings, GameAreaSettings,
-        GameLevelSettingsCollection, GameSettings, GameStartSettings, HighScoreBoard,
-        InputKeySettings, SpriteSheetAsset, StateBackgrounds, TextureCount, WeaponCollection,
+        AsteroidTextureCollection, DefaultLevelSettings, EndlessHighScoreBoard, EndlessSettings,
+        EnemyCollection, EnemyTextureCollection, GameAreaSettings, GameLevelSettingsCollection,
+        GameSettings, GameStartSettings, HighScoreBoard, InputKeySettings, PlayerTextureCollection,
+        SpriteSheetAsset, StateBackgrounds, TextureCount, WeaponCollection,
     },
+    rollback::RollbackApp,
     states::GameState,
 };
 
@@ -39,6 +41,17 @@ impl Plugin for GameAssetsPlugin {
                 .build()
                 .expect("failed to initialize high-scores"),
         );
+        app.insert_resource(
+            Persistent::<EndlessHighScoreBoard>::builder()
+                .name("endless highscore board")
+                .format(StorageFormat::Ron)
+                .path(state_dir.join("endless-highscores.ron"))
+                .default(EndlessHighScoreBoard::default())
+                .revertible(true)
+                .revert_to_default_on_deserialization_errors(true)
+                .build()
+                .expect("failed to initialize endless high-scores"),
+        );
 
         // register assets for debug
         app.register_type::<AsteroidPoolCollection>()
@@ -46,12 +59,15 @@ impl Plugin for GameAssetsPlugin {
             .register_type::<InputKeySettings>()
             .register_type::<WeaponCollection>()
             .register_type::<AmmonitionDepot>()
-            .register_type::<TextureCount>();
+            .register_type::<EnemyCollection>()
+            .register_type::<TextureCount>()
+            .rollback_component::<TextureCount>();
 
         // register ron asset loaders
         app.add_plugins((
             RonAssetPlugin::<AmmonitionDepot>::new(&["ammonition-depot.ron"]),
             RonAssetPlugin::<AsteroidPoolCollection>::new(&["asteroid-pool-collection.ron"]),
+            RonAssetPlugin::<EnemyCollection>::new(&["enemy-collection.ron"]),
             RonAssetPlugin::<GameSettings>::new(&["game-settings.ron"]),
             RonAssetPlugin::<InputKeySettings>::new(&["input-key-settings.ron"]),
             RonAssetPlugin::<WeaponCollection>::new(&["weapon-collection.ron"]),
@@ -62,7 +78,9 @@ impl Plugin for GameAssetsPlugin {
         // register assets that can be dynamically loaded, but are NOT registered through the RonAssetPlugin
         app.init_asset::<SpriteSheetAsset>()
             .init_asset::<AmmonitionTextureCollection>()
-            .init_asset::<AsteroidTextureCollection>();
+            .init_asset::<AsteroidTextureCollection>()
+            .init_asset::<EnemyTextureCollection>()
+            .init_asset::<PlayerTextureCollection>();
 
         // setup loading of assets
         app.configure_loading_state(
@@ -81,12 +99,16 @@ impl Plugin for GameAssetsPlugin {
                 .init_resource::<GameAreaSettings>()
                 .init_resource::<GameStartSettings>()
                 .init_resource::<DefaultLevelSettings>()
+                .init_resource::<EndlessSettings>()
                 .init_resource::<InputKeySettings>()
                 .init_resource::<WeaponCollection>()
                 .init_resource::<AmmonitionDepot>()
                 .init_resource::<AsteroidPoolCollection>()
                 .init_resource::<AmmonitionTextureCollection>()
                 .init_resource::<AsteroidTextureCollection>()
+                .init_resource::<EnemyCollection>()
+                .init_resource::<EnemyTextureCollection>()
+                .init_resource::<PlayerTextureCollection>()
                 .init_resource::<GameLevelSettingsCollection>(),
         );
     }
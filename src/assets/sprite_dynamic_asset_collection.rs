@@ -13,7 +13,8 @@ use bevy_asset_loader::prelude::*;
 use serde::{Deserialize, Serialize};
 
 use crate::assets::{
-    optional, AmmonitionTextureCollection, AsteroidTextureCollection, SpriteSheetAsset,
+    optional, AmmonitionTextureCollection, AsteroidTextureCollection, EnemyTextureCollection,
+    PlayerTextureCollection, SpriteSheetAsset,
 };
 
 #[derive(Asset, TypePath, Deserialize, Debug, Default, Clone)]
@@ -38,6 +39,8 @@ enum SpriteDynamicAsset {
     SpriteSheet(SpriteSheet),
     AsteroidTextureCollection(BTreeMap<String, SpriteSheet>),
     AmmonitionTextureCollection(BTreeMap<String, SpriteSheet>),
+    EnemyTextureCollection(BTreeMap<String, SpriteSheet>),
+    PlayerTextureCollection(BTreeMap<String, SpriteSheet>),
 }
 
 #[derive(Deserialize, Serialize, Debug, Clone, Reflect, PartialEq)]
@@ -140,6 +143,15 @@ pub struct Physics {
     pub rotation: Option<f32>,
 }
 
+impl Physics {
+    /// Radius of a circle around the origin of the entity enclosing the collider.
+    pub fn bounding_radius(&self) -> f32 {
+        self.position.unwrap_or_default().length()
+            + self.collider.bounding_radius()
+            + self.margin.unwrap_or_default()
+    }
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Reflect, Default)]
 enum Anchor {
     #[default]
@@ -222,6 +234,26 @@ pub enum ColliderType {
     Polygon { radius: f32, sides: u32 },
 }
 
+impl ColliderType {
+    /// Radius of a circle around the center of the collider enclosing it.
+    pub fn bounding_radius(&self) -> f32 {
+        match *self {
+            ColliderType::Circle(radius) => radius,
+            ColliderType::Ellipse { x, y } => x.max(y),
+            ColliderType::Rectangle { x, y } => Vec2::new(x, y).length() / 2.0,
+            ColliderType::RoundRectangle { x, y, radius } => {
+                Vec2::new(x, y).length() / 2.0 + radius
+            }
+            ColliderType::Capsule { radius, length } => length / 2.0 + radius,
+            ColliderType::CapsuleEndpoints { p1, p2, radius } => {
+                p1.length().max(p2.length()) + radius
+            }
+            ColliderType::Triangle { p1, p2, p3 } => p1.length().max(p2.length()).max(p3.length()),
+            ColliderType::Polygon { radius, .. } => radius,
+        }
+    }
+}
+
 impl DynamicAsset for SpriteDynamicAsset {
     fn load(&self, asset_server: &AssetServer) -> Vec<UntypedHandle> {
         match self {
@@ -234,7 +266,9 @@ impl DynamicAsset for SpriteDynamicAsset {
             }
 
             SpriteDynamicAsset::AsteroidTextureCollection(sprite_sheets)
-            | SpriteDynamicAsset::AmmonitionTextureCollection(sprite_sheets) => sprite_sheets
+            | SpriteDynamicAsset::AmmonitionTextureCollection(sprite_sheets)
+            | SpriteDynamicAsset::EnemyTextureCollection(sprite_sheets)
+            | SpriteDynamicAsset::PlayerTextureCollection(sprite_sheets) => sprite_sheets
                 .iter()
                 .map(|(_, SpriteSheet { texture, .. })| {
                     asset_server.load_untyped(texture).untyped()
@@ -274,6 +308,22 @@ impl DynamicAsset for SpriteDynamicAsset {
                     AmmonitionTextureCollection::new(spritesheets),
                 )))
             }
+
+            SpriteDynamicAsset::EnemyTextureCollection(spritesheets) => {
+                let spritesheets = self.build_spritesheet_vec(spritesheets, world);
+                Ok(DynamicAssetType::Single(self.add_asset(
+                    world,
+                    EnemyTextureCollection::new(spritesheets),
+                )))
+            }
+
+            SpriteDynamicAsset::PlayerTextureCollection(spritesheets) => {
+                let spritesheets = self.build_spritesheet_vec(spritesheets, world);
+                Ok(DynamicAssetType::Single(self.add_asset(
+                    world,
+                    PlayerTextureCollection::new(spritesheets),
+                )))
+            }
         }
     }
 }
@@ -27,6 +27,21 @@ pub struct SpriteSheetAsset {
     pub physics: Option<Vec<Physics>>,
 }
 
+impl SpriteSheetAsset {
+    /// Radius of a circle around the entity enclosing all of its colliders, or the sprite if it
+    /// has none.
+    pub fn bounding_radius(&self) -> f32 {
+        let radius = match &self.physics {
+            Some(physics) if !physics.is_empty() => physics
+                .iter()
+                .map(Physics::bounding_radius)
+                .fold(0.0, f32::max),
+            _ => self.size.unwrap_or_default().max_element() / 2.0,
+        };
+        radius * self.transform.scale.truncate().max_element()
+    }
+}
+
 #[derive(Component, Reflect, Debug, Clone, Copy)]
 pub struct TextureCount(usize);
 
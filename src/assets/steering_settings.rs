@@ -0,0 +1,72 @@
+use bevy::prelude::*;
+use serde::Deserialize;
+
+/// Steering behaviors of an entity moving on its own, combined by their weights.
+#[derive(Reflect, Deserialize, Debug, Clone)]
+pub struct SteeringSettings {
+    /// speed the behaviors are steering towards
+    pub max_speed: f32,
+    /// maximum change in velocity per second
+    pub max_force: f32,
+    /// rotate the entity in the direction it's moving
+    #[serde(default = "SteeringSettings::default_face_velocity")]
+    pub face_velocity: bool,
+    pub behaviors: Vec<WeightedSteeringBehavior>,
+}
+
+impl SteeringSettings {
+    fn default_face_velocity() -> bool {
+        true
+    }
+}
+
+#[derive(Reflect, Deserialize, Debug, Clone)]
+pub struct WeightedSteeringBehavior {
+    pub behavior: SteeringBehavior,
+    #[serde(default = "WeightedSteeringBehavior::default_weight")]
+    pub weight: f32,
+}
+
+impl WeightedSteeringBehavior {
+    fn default_weight() -> f32 {
+        1.0
+    }
+}
+
+#[derive(Reflect, Deserialize, Debug, Clone)]
+pub enum SteeringBehavior {
+    /// Move straight towards the target
+    Seek { target: SteeringTarget },
+    /// Move away from the target, when it is closer than `radius`
+    Flee { target: SteeringTarget, radius: f32 },
+    /// Move towards where the target is going to be, looking at most `prediction` seconds ahead
+    Pursue {
+        target: SteeringTarget,
+        prediction: f32,
+    },
+    /// Move around randomly, by steering towards a point on a circle with the given `radius`,
+    /// `distance` units ahead. The point moves around the circle by up to `jitter` radians per
+    /// second.
+    Wander {
+        distance: f32,
+        radius: f32,
+        jitter: f32,
+    },
+    /// Circle around the target at the given `radius`
+    Orbit {
+        target: SteeringTarget,
+        radius: f32,
+        #[serde(default)]
+        clockwise: bool,
+    },
+    /// Steer away from asteroids less than `distance` units ahead
+    Avoid { distance: f32 },
+}
+
+#[derive(Reflect, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[allow(clippy::enum_variant_names)]
+pub enum SteeringTarget {
+    ClosestPlayer,
+    ClosestAsteroid,
+    ClosestEnemy,
+}
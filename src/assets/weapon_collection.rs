@@ -27,6 +27,9 @@ pub struct WeaponInfo {
     pub default_ammonition: Vec<AmmonitionSelection>,
     #[serde(with = "optional", skip_serializing_if = "Option::is_none", default)]
     pub audio: Option<String>,
+    /// change in velocity pushing the shooter backwards, every time the weapon is fired
+    #[serde(default)]
+    pub recoil: f32,
 }
 
 #[derive(Reflect, Deserialize, Debug, Clone)]
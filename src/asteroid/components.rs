@@ -1,7 +1,10 @@
 use bevy::prelude::*;
-use derive_more::derive::{Deref, From};
+use derive_more::derive::{Deref, DerefMut, From};
 
-use crate::assets::AsteroidHitBehavior;
+use crate::{
+    assets::{AsteroidHitBehavior, AsteroidMaterial},
+    asteroid::AsteroidSpawnNewEvent,
+};
 
 #[derive(Component, Debug, Clone, Copy)]
 pub struct Asteroid;
@@ -11,3 +14,40 @@ pub struct AsteroidSprite;
 
 #[derive(Component, Debug, Clone, Deref, From)]
 pub struct HitBehavior(Vec<AsteroidHitBehavior>);
+
+/// Behavior when the asteroid is hit, but not destroyed.
+#[derive(Component, Debug, Clone, Deref, From)]
+pub struct DamageBehavior(Vec<AsteroidHitBehavior>);
+
+/// Remaining hit points of an asteroid.
+#[derive(Component, Debug, Clone, Copy, Deref, DerefMut, From)]
+pub struct HitPoints(u32);
+
+/// Damage of less than a hit point, left over from hits with
+/// [crate::assets::AmmonitionEffect::Reduced].
+#[derive(Component, Debug, Clone, Copy, Default, Deref, DerefMut, From)]
+pub struct PartialDamage(f32);
+
+/// The color of the asteroid sprite when not tinted, e.g. by a flash.
+#[derive(Component, Debug, Clone, Copy, Deref, From)]
+pub struct BaseColor(Color);
+
+/// What the asteroid is made of.
+#[derive(Component, Debug, Clone, Copy, Deref, From)]
+pub struct SurfaceMaterial(AsteroidMaterial);
+
+/// Key of the pool in the [crate::assets::AsteroidPoolCollection] the asteroid was spawned from.
+#[derive(Component, Debug, Clone, Deref, From)]
+pub struct PoolKey(String);
+
+/// An asteroid flying in from outside the game area, it starts wrapping once inside.
+#[derive(Component, Debug, Clone, Copy)]
+pub struct EnteringGameArea;
+
+/// Warning blinking at the edge of the game area, the asteroid flies in once the timer finishes.
+#[derive(Component, Debug, Clone)]
+pub struct EntryTelegraph {
+    pub timer: Timer,
+    pub blink: Timer,
+    pub event: AsteroidSpawnNewEvent,
+}
@@ -0,0 +1,160 @@
+use core::time::Duration;
+
+use avian2d::prelude::*;
+use bevy::prelude::*;
+use bevy_turborand::{DelegatedRng, RngComponent};
+
+use crate::{
+    assets::GameAreaSettings,
+    asteroid::{
+        AsteroidCount, AsteroidSet, AsteroidSpawnNewEvent, EnteringGameArea, EntryTelegraph,
+    },
+    constants::{ASTEROID_Z_RANGE, ENTRY_TELEGRAPH_Z_POS},
+    movement::Wrapping,
+    rollback::RollbackApp,
+    states::{GameState, PlayState},
+    utils::RngComponentExt,
+};
+
+/// Asteroids without speed of their own still need to make it into the game area.
+const MIN_ENTRY_SPEED: f32 = 40.0;
+const TELEGRAPH_BLINK_INTERVAL: f32 = 0.15;
+const TELEGRAPH_SIZE: Vec2 = Vec2::new(48.0, 6.0);
+
+pub fn init_entry(app: &mut App) {
+    app.add_systems(
+        FixedUpdate,
+        (update_entry_telegraphs, finish_entering_game_area)
+            .run_if(in_state(PlayState::Running))
+            .in_set(AsteroidSet),
+    )
+    .observe(on_entry_telegraph_added)
+    .observe(on_entry_telegraph_removed)
+    .rollback_component::<EnteringGameArea>()
+    .rollback_component::<EntryTelegraph>();
+}
+
+/// Announces an asteroid entering the game area at a random edge, the asteroid itself is spawned
+/// once the `warning` is over.
+///
+/// The asteroid starts hidden behind the border, heading for the middle of the game area.
+pub fn spawn_entry_telegraph(
+    mut event: AsteroidSpawnNewEvent,
+    warning: Duration,
+    game_area_settings: &GameAreaSettings,
+    playing_field: Entity,
+    rand: &mut RngComponent,
+    commands: &mut Commands,
+) {
+    let game_area = &game_area_settings.game_area;
+    let border = &game_area_settings.border_area;
+    let (min, max) = (game_area.min().truncate(), game_area.max().truncate());
+
+    let t = rand.f32();
+    let (edge_position, outwards) = match rand.usize(0..4) {
+        0 => (Vec2::new(min.x.lerp(max.x, t), min.y), Vec2::NEG_Y),
+        1 => (Vec2::new(min.x.lerp(max.x, t), max.y), Vec2::Y),
+        2 => (Vec2::new(min.x, min.y.lerp(max.y, t)), Vec2::NEG_X),
+        _ => (Vec2::new(max.x, min.y.lerp(max.y, t)), Vec2::X),
+    };
+    let depth = border.min.min_element().min(border.max.min_element()) / 2.0;
+    let position = edge_position + outwards * depth;
+
+    let center = (min + max) / 2.0;
+    let target = center
+        + Vec2::new(
+            rand.f32_normalized() * game_area.width() / 4.0,
+            rand.f32_normalized() * game_area.height() / 4.0,
+        );
+    let speed = event.linear_velocity.length().max(MIN_ENTRY_SPEED);
+    event.position = position.extend(-rand.f32_range(ASTEROID_Z_RANGE));
+    event.linear_velocity = LinearVelocity((target - position).normalize_or_zero() * speed);
+    event.entering = true;
+
+    let size = if outwards.x == 0.0 {
+        TELEGRAPH_SIZE
+    } else {
+        TELEGRAPH_SIZE.yx()
+    };
+    let telegraph_position = edge_position - outwards * TELEGRAPH_SIZE.y;
+    commands.entity(playing_field).with_children(|commands| {
+        commands.spawn((
+            StateScoped(GameState::Playing),
+            Name::new("Asteroid Entry Warning"),
+            EntryTelegraph {
+                timer: Timer::new(warning, TimerMode::Once),
+                blink: Timer::from_seconds(TELEGRAPH_BLINK_INTERVAL, TimerMode::Repeating),
+                event,
+            },
+            SpriteBundle {
+                sprite: Sprite {
+                    color: Color::srgba(1.0, 0.3, 0.1, 0.8),
+                    custom_size: Some(size),
+                    ..default()
+                },
+                transform: Transform::from_translation(
+                    telegraph_position.extend(ENTRY_TELEGRAPH_Z_POS),
+                ),
+                ..default()
+            },
+        ));
+    });
+}
+
+fn update_entry_telegraphs(
+    time: Res<Time>,
+    mut query: Query<(Entity, &mut EntryTelegraph, &mut Visibility, &Parent)>,
+    mut commands: Commands,
+) {
+    for (entity, mut telegraph, mut visibility, playing_field) in query.iter_mut() {
+        if telegraph.blink.tick(time.delta()).just_finished() {
+            *visibility = match *visibility {
+                Visibility::Hidden => Visibility::Visible,
+                _ => Visibility::Hidden,
+            };
+        }
+        if telegraph.timer.tick(time.delta()).just_finished() {
+            // telegraphs only run out while the game is running
+            let mut event = telegraph.event.clone();
+            event.playstate = Some(PlayState::Running);
+            trace!(?event, "asteroid entering the game area");
+            commands.trigger_targets(event, playing_field.get());
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
+
+/// Lets asteroids wrap around the edges, once they made it into the game area.
+fn finish_entering_game_area(
+    query: Query<(Entity, &Position), With<EnteringGameArea>>,
+    game_area_settings: Res<GameAreaSettings>,
+    mut commands: Commands,
+) {
+    for (asteroid, position) in query.iter() {
+        if game_area_settings.game_area.contains(position.0) {
+            commands
+                .entity(asteroid)
+                .remove::<EnteringGameArea>()
+                .insert(Wrapping);
+        }
+    }
+}
+
+/// Announced asteroids count as left in the level, so it isn't cleared before they arrive.
+fn on_entry_telegraph_added(
+    _trigger: Trigger<OnAdd, EntryTelegraph>,
+    mut counter: ResMut<AsteroidCount>,
+) {
+    **counter += 1;
+    debug!(asteroid_count = **counter);
+}
+
+fn on_entry_telegraph_removed(
+    _trigger: Trigger<OnRemove, EntryTelegraph>,
+    mut counter: ResMut<AsteroidCount>,
+) {
+    if **counter > 0 {
+        **counter -= 1;
+        debug!(asteroid_count = **counter);
+    }
+}
@@ -2,7 +2,10 @@ use avian2d::prelude::{AngularVelocity, LinearVelocity};
 use bevy::prelude::*;
 
 use crate::{
-    assets::{AsteroidHitBehavior, SpriteSheetAsset},
+    assets::{
+        AsteroidEntry, AsteroidFormation, AsteroidHitBehavior, AsteroidMaterial, AsteroidSelection,
+        SpriteSheetAsset, SteeringSettings,
+    },
     states::{GameState, PlayState},
 };
 
@@ -12,15 +15,32 @@ pub struct AsteroidSpawnNewEvent {
     pub playstate: Option<PlayState>,
 
     pub position: Vec3,
+    /// the asteroid flies in from outside the game area, see [crate::assets::AsteroidEntry]
+    pub entering: bool,
     pub linear_velocity: LinearVelocity,
     pub angular_velocity: AngularVelocity,
 
+    /// key of the pool in the [crate::assets::AsteroidPoolCollection]
+    pub pool: String,
+    pub steering: Option<SteeringSettings>,
+    pub material: AsteroidMaterial,
+    pub hit_points: u32,
     pub hit_behavior: Vec<AsteroidHitBehavior>,
+    pub damage_behavior: Vec<AsteroidHitBehavior>,
 
     pub spritesheet: SpriteSheetAsset,
     pub atlas_index: usize,
 }
 
+/// Triggered on the [crate::PlayingField] to spawn asteroids in the middle of a level.
+#[derive(Event, Debug, Clone)]
+pub struct AsteroidSpawnWaveEvent {
+    pub count: usize,
+    pub select_from: Vec<AsteroidSelection>,
+    pub entry: AsteroidEntry,
+    pub formation: AsteroidFormation,
+}
+
 #[derive(Event, Debug, Clone)]
 pub struct AsteroidRemoveEvent {
     pub asteroid: Entity,
@@ -30,4 +50,16 @@ pub struct AsteroidRemoveEvent {
 pub struct AsteroidHitEvent {
     pub position: Vec3,
     pub players: Vec<Entity>,
+    /// normalized direction of the impact, or [Vec2::ZERO] if unknown
+    pub direction: Vec2,
+    /// hit points to remove from the asteroid
+    pub damage: u32,
+}
+
+/// Sent when the hit points of an asteroid are used up.
+#[derive(Event, Debug, Clone)]
+pub struct AsteroidDestroyedEvent {
+    /// key of the pool in the [crate::assets::AsteroidPoolCollection] the asteroid was spawned
+    /// from
+    pub pool: String,
 }
@@ -0,0 +1,133 @@
+use core::f32::consts::TAU;
+
+use avian2d::prelude::*;
+use bevy::prelude::*;
+use bevy_turborand::{DelegatedRng, RngComponent};
+
+use crate::{
+    assets::{AsteroidFormation, AsteroidLayout, AsteroidVelocityPattern},
+    asteroid::placement::OccupiedAreas,
+};
+
+/// How often an asteroid is moved away from its place in the layout, before it is left out.
+const MAX_PLACEMENT_ATTEMPTS: usize = 16;
+
+/// Places the asteroids of an [AsteroidFormation], keeping their colliders out of the
+/// [OccupiedAreas].
+pub(super) struct FormationPlacement<'a> {
+    formation: &'a AsteroidFormation,
+    center: Vec2,
+    places: Vec<Vec2>,
+}
+
+impl<'a> FormationPlacement<'a> {
+    pub fn new(formation: &'a AsteroidFormation, count: usize, rand: &mut RngComponent) -> Self {
+        let places = layout_places(&formation.layout, count, rand);
+        if places.len() < count {
+            warn!(
+                count,
+                places = places.len(),
+                "Not enough places in the formation, leaving out asteroids"
+            );
+        }
+        Self {
+            formation,
+            center: formation.layout.center(),
+            places,
+        }
+    }
+
+    /// Position of the asteroid with the given index and collider radius, or `None` if there is
+    /// no free place for it.
+    pub fn place(
+        &self,
+        index: usize,
+        radius: f32,
+        occupied: &mut OccupiedAreas,
+        rand: &mut RngComponent,
+    ) -> Option<Vec2> {
+        let place = *self.places.get(index)?;
+        for attempt in 0..MAX_PLACEMENT_ATTEMPTS {
+            // look further away from the place in the layout with each attempt
+            let spread = self.formation.jitter
+                + 2.0 * radius * attempt as f32 / MAX_PLACEMENT_ATTEMPTS as f32;
+            let position = place + random_in_circle(spread, rand);
+            if occupied.is_free(position, radius) {
+                occupied.occupy(position, radius);
+                return Some(position);
+            }
+        }
+        debug!(index, ?place, "No free place for asteroid in formation");
+        None
+    }
+
+    /// Turns the velocity of an asteroid at the position as given by the [AsteroidVelocityPattern].
+    pub fn velocity(&self, position: Vec2, velocity: LinearVelocity) -> LinearVelocity {
+        let to_center = (self.center - position).try_normalize();
+        let direction = match self.formation.velocity {
+            AsteroidVelocityPattern::Random => None,
+            AsteroidVelocityPattern::Orbit { clockwise: false } => to_center.map(|d| -d.perp()),
+            AsteroidVelocityPattern::Orbit { clockwise: true } => to_center.map(|d| d.perp()),
+            AsteroidVelocityPattern::Converge => to_center,
+            AsteroidVelocityPattern::Diverge => to_center.map(|d| -d),
+            AsteroidVelocityPattern::Heading(heading) => heading.try_normalize(),
+        };
+        match direction {
+            Some(direction) => LinearVelocity(direction * velocity.length()),
+            None => velocity,
+        }
+    }
+}
+
+/// The places of the asteroids in the layout, without jitter.
+fn layout_places(layout: &AsteroidLayout, count: usize, rand: &mut RngComponent) -> Vec<Vec2> {
+    // position of the asteroid along the layout, from 0 to 1
+    let fraction = |index: usize| match count {
+        0 | 1 => 0.5,
+        _ => index as f32 / (count - 1) as f32,
+    };
+    match layout {
+        AsteroidLayout::Random => vec![],
+        AsteroidLayout::Ring { center, radius } => (0..count)
+            .map(|index| *center + Vec2::from_angle(TAU * index as f32 / count as f32) * *radius)
+            .collect(),
+        AsteroidLayout::Line { from, to } => (0..count)
+            .map(|index| from.lerp(*to, fraction(index)))
+            .collect(),
+        AsteroidLayout::Grid {
+            center,
+            columns,
+            spacing,
+        } => {
+            let columns = (*columns).max(1);
+            let rows = count.div_ceil(columns);
+            let size = Vec2::new(columns as f32 - 1.0, rows as f32 - 1.0);
+            (0..count)
+                .map(|index| {
+                    let cell = Vec2::new((index % columns) as f32, (index / columns) as f32);
+                    *center + (cell - size / 2.0) * Vec2::new(1.0, -1.0) * *spacing
+                })
+                .collect()
+        }
+        AsteroidLayout::Spiral {
+            center,
+            inner_radius,
+            outer_radius,
+            turns,
+        } => (0..count)
+            .map(|index| {
+                let t = if count == 1 { 0.0 } else { fraction(index) };
+                *center + Vec2::from_angle(TAU * turns * t) * inner_radius.lerp(*outer_radius, t)
+            })
+            .collect(),
+        AsteroidLayout::Cluster { center, radius } => (0..count)
+            .map(|_| *center + random_in_circle(*radius, rand))
+            .collect(),
+        AsteroidLayout::Points(points) => points.iter().take(count).copied().collect(),
+    }
+}
+
+/// A uniformly distributed random point inside a circle around the origin.
+fn random_in_circle(radius: f32, rand: &mut RngComponent) -> Vec2 {
+    Vec2::from_angle(rand.f32() * TAU) * rand.f32().sqrt() * radius
+}
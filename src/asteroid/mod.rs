@@ -1,7 +1,11 @@
 mod components;
+mod entry;
 mod events;
+mod formation;
+mod placement;
 mod plugin;
 mod resources;
+mod speed_ramp;
 mod systems;
 
 #[cfg(feature = "dbg_colliders")]
@@ -0,0 +1,34 @@
+use bevy::prelude::*;
+
+/// Minimum gap between the colliders of newly placed asteroids.
+const MIN_SPACING: f32 = 8.0;
+
+/// Areas taken by asteroids already in play or placed so far, or kept free around the players.
+///
+/// Colliders are approximated by their bounding circle, see
+/// [crate::assets::SpriteSheetAsset::bounding_radius].
+#[derive(Debug, Clone, Default)]
+pub(super) struct OccupiedAreas(Vec<(Vec2, f32)>);
+
+impl OccupiedAreas {
+    /// Keeps circles with the radius around the positions free, without any spacing.
+    pub fn keep_free(positions: impl IntoIterator<Item = Vec2>, radius: f32) -> Self {
+        Self(
+            positions
+                .into_iter()
+                .map(|position| (position, radius - MIN_SPACING))
+                .collect(),
+        )
+    }
+
+    /// Whether a collider with the radius at the position keeps its distance to all others.
+    pub fn is_free(&self, position: Vec2, radius: f32) -> bool {
+        self.0.iter().all(|(other, other_radius)| {
+            other.distance(position) >= other_radius + radius + MIN_SPACING
+        })
+    }
+
+    pub fn occupy(&mut self, position: Vec2, radius: f32) {
+        self.0.push((position, radius));
+    }
+}
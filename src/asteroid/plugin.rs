@@ -3,11 +3,16 @@ use smart_default::SmartDefault;
 
 use crate::{
     asteroid::{
-        despawn_all_asteroids, detect_asteroid_hits, init_asteroid_counter, on_asteroid_added,
-        on_asteroid_hit, on_asteroid_removed, on_asteroid_spawn_new, on_remove_asteroid,
-        resume_asteroid_movement, spawn_level_asteroids, AsteroidCount, AsteroidRemoveEvent,
+        despawn_all_asteroids, detect_asteroid_hits, entry::init_entry, init_asteroid_counter,
+        no_asteroids_left, on_asteroid_added, on_asteroid_hit, on_asteroid_removed,
+        on_asteroid_spawn_new, on_asteroid_spawn_wave, on_remove_asteroid,
+        resume_asteroid_movement, spawn_level_asteroids, speed_ramp::init_speed_ramp, Asteroid,
+        AsteroidCount, AsteroidDestroyedEvent, AsteroidRemoveEvent, AsteroidSprite, BaseColor,
+        DamageBehavior, HitBehavior, HitPoints, PartialDamage, PoolKey, SurfaceMaterial,
     },
-    levels::GameLevelsSet,
+    levels::{versus_mode, GameLevelsSet},
+    projectile::ProjectileSet,
+    rollback::RollbackApp,
     PlayState,
 };
 
@@ -23,7 +28,19 @@ impl Plugin for AsteroidPlugin {
         super::dbg_colliders::setup_dbg(app);
 
         app.init_resource::<AsteroidCount>()
-            .add_event::<AsteroidRemoveEvent>();
+            .add_event::<AsteroidRemoveEvent>()
+            .add_event::<AsteroidDestroyedEvent>();
+
+        app.rollback_component::<Asteroid>()
+            .rollback_component::<AsteroidSprite>()
+            .rollback_component::<HitBehavior>()
+            .rollback_component::<DamageBehavior>()
+            .rollback_component::<HitPoints>()
+            .rollback_component::<PartialDamage>()
+            .rollback_component::<BaseColor>()
+            .rollback_component::<SurfaceMaterial>()
+            .rollback_component::<PoolKey>()
+            .rollback_resource::<AsteroidCount>();
 
         app.add_systems(
             OnEnter(PlayState::StartNewGame),
@@ -38,7 +55,12 @@ impl Plugin for AsteroidPlugin {
         )
         .add_systems(
             OnExit(PlayState::StartNextLevel),
-            (init_asteroid_counter, spawn_level_asteroids)
+            // asteroids may be left over when the level objective was not to clear them all
+            (
+                despawn_all_asteroids,
+                init_asteroid_counter,
+                spawn_level_asteroids,
+            )
                 .chain()
                 .in_set(AsteroidSet)
                 .after(GameLevelsSet),
@@ -48,21 +70,32 @@ impl Plugin for AsteroidPlugin {
             resume_asteroid_movement.in_set(AsteroidSet),
         )
         .add_systems(
-            PostUpdate,
-            on_remove_asteroid
+            FixedPostUpdate,
+            (detect_asteroid_hits, on_remove_asteroid)
+                .chain()
                 .run_if(in_state(PlayState::Running))
                 .in_set(AsteroidSet),
         )
         .add_systems(
-            Update,
-            (detect_asteroid_hits)
+            FixedPostUpdate,
+            // asteroids stay in play as hazards during a versus match
+            spawn_level_asteroids
+                .after(on_remove_asteroid)
                 .run_if(in_state(PlayState::Running))
+                .run_if(versus_mode)
+                .run_if(no_asteroids_left)
                 .in_set(AsteroidSet),
-        );
+        )
+        // hits are detected in the same tick as the collisions of the projectiles
+        .configure_sets(FixedPostUpdate, AsteroidSet.after(ProjectileSet));
 
         app.observe(on_asteroid_spawn_new)
+            .observe(on_asteroid_spawn_wave)
             .observe(on_asteroid_hit)
             .observe(on_asteroid_added)
             .observe(on_asteroid_removed);
+
+        init_entry(app);
+        init_speed_ramp(app);
     }
 }
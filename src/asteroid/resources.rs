@@ -1,5 +1,10 @@
 use bevy::prelude::*;
 use derive_more::{Constructor, Debug, Deref, DerefMut};
+use smart_default::SmartDefault;
 
 #[derive(Resource, Debug, Default, Clone, Copy, Constructor, Deref, DerefMut)]
 pub struct AsteroidCount(usize);
+
+/// Multiplier of the asteroid velocities, see [crate::assets::AsteroidSpeedRampSettings].
+#[derive(Resource, Debug, SmartDefault, Clone, Copy, Deref, DerefMut)]
+pub struct AsteroidSpeedRamp(#[default(1.0)] f32);
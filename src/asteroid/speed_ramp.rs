@@ -0,0 +1,86 @@
+use avian2d::prelude::*;
+use bevy::prelude::*;
+
+use crate::{
+    assets::GameLevelSettings,
+    asteroid::{
+        spawn_level_asteroids, Asteroid, AsteroidCount, AsteroidSet, AsteroidSpeedRamp,
+        EntryTelegraph,
+    },
+    levels::{GameLevelsSet, LevelTimer},
+    movement::PausedLinearVelocity,
+    rollback::RollbackApp,
+    states::PlayState,
+};
+
+pub fn init_speed_ramp(app: &mut App) {
+    app.init_resource::<AsteroidSpeedRamp>()
+        .rollback_resource::<AsteroidSpeedRamp>()
+        .add_systems(
+            OnEnter(PlayState::StartNewGame),
+            reset_speed_ramp
+                .before(spawn_level_asteroids)
+                .in_set(AsteroidSet),
+        )
+        .add_systems(
+            OnExit(PlayState::StartNextLevel),
+            reset_speed_ramp
+                .before(spawn_level_asteroids)
+                .in_set(AsteroidSet),
+        )
+        .add_systems(
+            FixedUpdate,
+            ramp_asteroid_speed
+                .after(GameLevelsSet)
+                .run_if(in_state(PlayState::Running))
+                .in_set(AsteroidSet),
+        );
+}
+
+fn reset_speed_ramp(mut speed_ramp: ResMut<AsteroidSpeedRamp>) {
+    *speed_ramp = AsteroidSpeedRamp::default();
+}
+
+/// Follows the [crate::assets::AsteroidSpeedRampSettings] of the level, scaling the velocity of
+/// every asteroid by the change of the multiplier.
+///
+/// Velocities of paused asteroids, and of asteroids about to enter the game area, are scaled
+/// as well. Asteroids spawned later on get the current multiplier on top of their pool speed.
+fn ramp_asteroid_speed(
+    level_settings: Res<GameLevelSettings>,
+    level_timer: Res<LevelTimer>,
+    asteroid_counter: Res<AsteroidCount>,
+    mut speed_ramp: ResMut<AsteroidSpeedRamp>,
+    mut asteroid_query: Query<
+        (
+            Option<&mut LinearVelocity>,
+            Option<&mut PausedLinearVelocity>,
+        ),
+        With<Asteroid>,
+    >,
+    mut telegraph_query: Query<&mut EntryTelegraph>,
+) {
+    let Some(settings) = &level_settings.speed_ramp else {
+        return;
+    };
+    let multiplier = settings.multiplier(level_timer.elapsed(), **asteroid_counter);
+    if multiplier == **speed_ramp {
+        return;
+    }
+
+    let scale = multiplier / **speed_ramp;
+    trace!(multiplier, scale, "Ramping asteroid speed");
+    for (velocity, paused_velocity) in asteroid_query.iter_mut() {
+        if let Some(mut velocity) = velocity {
+            velocity.0 *= scale;
+        }
+        // unpausing restores this velocity, it has to keep up with the multiplier
+        if let Some(mut paused_velocity) = paused_velocity {
+            paused_velocity.0 .0 *= scale;
+        }
+    }
+    for mut telegraph in telegraph_query.iter_mut() {
+        telegraph.event.linear_velocity.0 *= scale;
+    }
+    **speed_ramp = multiplier;
+}
@@ -3,51 +3,70 @@ use std::f32::consts::TAU;
 use avian2d::prelude::*;
 use bevy::{audio::Volume, prelude::*};
 use bevy_turborand::{DelegatedRng, RngComponent};
+use bevy_tweening::{
+    lens::SpriteColorLens, Animator, EaseFunction, RepeatCount, RepeatStrategy, Tween,
+};
 
 use crate::{
     assets::{
-        AsteroidDisplacement, AsteroidHitBehavior, AsteroidPool, AsteroidPoolCollection,
-        AsteroidSpeedRange, AsteroidSplitCount, AsteroidSplitSelectionExt,
-        AsteroidTextureCollection, AsteroidTextureSelection, EntitySpriteSheetCommands,
-        GameAreaSettings, GameLevelSettings, PlayerSettings,
+        AmmonitionDepot, AsteroidDisplacement, AsteroidEntry, AsteroidFormation,
+        AsteroidHitBehavior, AsteroidLayout, AsteroidPhysics, AsteroidPool, AsteroidPoolCollection,
+        AsteroidSelection, AsteroidSpeedRange, AsteroidSplitCount, AsteroidSplitSelectionExt,
+        AsteroidSplitVelocity, AsteroidTextureCollection, AsteroidTextureSelection,
+        EntitySpriteSheetCommands, GameAreaSettings, GameLevelSettings, PlayerSettings,
     },
     asteroid::{
-        Asteroid, AsteroidCount, AsteroidHitEvent, AsteroidRemoveEvent, AsteroidSpawnNewEvent,
-        AsteroidSprite, HitBehavior,
+        entry::spawn_entry_telegraph, formation::FormationPlacement, placement::OccupiedAreas,
+        Asteroid, AsteroidCount, AsteroidDestroyedEvent, AsteroidHitEvent, AsteroidRemoveEvent,
+        AsteroidSpawnNewEvent, AsteroidSpawnWaveEvent, AsteroidSpeedRamp, AsteroidSprite,
+        BaseColor, DamageBehavior, EnteringGameArea, EntryTelegraph, HitBehavior, HitPoints,
+        PartialDamage, PoolKey, SurfaceMaterial,
     },
     constants::ASTEROID_Z_RANGE,
-    movement::{GameArea, PauseMovement, Wrapping},
-    player::AddToScoreEvent,
+    movement::{GameArea, PauseMovement, Steering, Wrapping},
+    player::{AddToScoreEvent, OutOfLives, Player},
     projectile::ProjectileCollisionEvent,
     states::PlayState,
-    utils::RngComponentExt,
+    utils::{IntoOrderedGroups, RngComponentExt},
     CollisionLayer, GameState, PlayingField,
 };
 
+/// How often [random_spawn_position] looks for a free position, before giving up.
+const MAX_SPAWN_ATTEMPTS: usize = 100;
+/// Partial damage this close to a full hit point counts as one, e.g. three hits of a third.
+const DAMAGE_TOLERANCE: f32 = 1e-4;
+
 pub fn resume_asteroid_movement(mut commands: Commands, query: Query<Entity, With<Asteroid>>) {
     for entity in query.iter() {
         commands.entity(entity).remove::<PauseMovement>();
     }
 }
 
-pub fn despawn_all_asteroids(mut commands: Commands, query: Query<Entity, With<Asteroid>>) {
+pub fn despawn_all_asteroids(
+    mut commands: Commands,
+    query: Query<Entity, Or<(With<Asteroid>, With<EntryTelegraph>)>>,
+) {
     debug!("despawning old asteroids");
     for asteroid in query.iter() {
         commands.entity(asteroid).despawn_recursive();
     }
 }
 
-/// System responsible for spawning the asteroids at the beginning of a new level.
+/// System responsible for spawning the asteroids at the beginning of a new level, and for
+/// replenishing them during a versus match.
 /// [despawn_all_asteroids]
 #[allow(clippy::too_many_arguments)]
 pub fn spawn_level_asteroids(
     mut playing_field: Query<(Entity, &mut RngComponent), With<PlayingField>>,
+    player_query: Query<&Position, (With<Player>, Without<OutOfLives>)>,
+    asteroid_query: Query<&ColliderAabb, With<AsteroidSprite>>,
     mut commands: Commands,
     player_settings: Res<PlayerSettings>,
     level_settings: Res<GameLevelSettings>,
     game_area_settings: Res<GameAreaSettings>,
     asteroid_pool_collection: Res<AsteroidPoolCollection>,
     asteroid_spritesheets: Res<AsteroidTextureCollection>,
+    speed_ramp: Res<AsteroidSpeedRamp>,
     playstate: Res<State<PlayState>>,
 ) {
     let (playing_field, mut rand) = playing_field.single_mut();
@@ -58,46 +77,74 @@ pub fn spawn_level_asteroids(
         AsteroidSplitCount::Range { start, end } => rand.usize(start..end),
     };
 
+    let players = player_query
+        .iter()
+        .map(|player| player.0)
+        .collect::<Vec<_>>();
+    let in_play = asteroids_in_play(&asteroid_query);
     debug!(asteroid_count, "Spawning level asteroids");
-    for _ in 0..asteroid_count {
-        // calculate asteroid spawn position
-        let position = {
-            let game_area = &game_area_settings.game_area;
-            let player_pos = player_settings.spawn_position;
-            let min_distance = player_settings.safe_radius;
-
-            loop {
-                let angle = rand.f32_range(0.0..TAU);
-                let intersection = calc_intersection(player_pos, angle, game_area);
-                let max_distance = player_pos.distance(intersection);
-                if max_distance > min_distance {
-                    let distance = rand.f32_range(min_distance..max_distance);
-                    break (Vec2::from_angle(angle) * distance)
-                        .extend(-rand.f32_range(ASTEROID_Z_RANGE));
-                }
-            }
-        };
-
-        let Some(pool) = asteroid_startup_settings
-            .select_from
-            .pick_random_pool(&mut rand, &asteroid_pool_collection)
-        else {
-            warn!("did not find an asteroid pool");
-            continue;
-        };
+    spawn_asteroids(
+        asteroid_count,
+        &asteroid_startup_settings.select_from,
+        asteroid_startup_settings.entry,
+        &asteroid_startup_settings.formation,
+        playing_field,
+        &players,
+        &in_play,
+        &player_settings,
+        &game_area_settings,
+        level_settings.asteroid_speed * **speed_ramp,
+        **playstate,
+        &asteroid_pool_collection,
+        &asteroid_spritesheets,
+        &mut rand,
+        &mut commands,
+    );
+}
 
-        spawn_asteroid_from_pool(
-            GameState::Playing,
-            Some(**playstate),
-            playing_field,
-            position,
-            pool,
-            None,
-            &asteroid_spritesheets,
-            &mut rand,
-            &mut commands,
-        );
-    }
+/// Spawns asteroids in the middle of a level, see [crate::director].
+#[allow(clippy::too_many_arguments)]
+pub fn on_asteroid_spawn_wave(
+    trigger: Trigger<AsteroidSpawnWaveEvent>,
+    mut rand: Query<&mut RngComponent, With<PlayingField>>,
+    player_query: Query<&Position, (With<Player>, Without<OutOfLives>)>,
+    asteroid_query: Query<&ColliderAabb, With<AsteroidSprite>>,
+    player_settings: Res<PlayerSettings>,
+    level_settings: Res<GameLevelSettings>,
+    game_area_settings: Res<GameAreaSettings>,
+    asteroid_pool_collection: Res<AsteroidPoolCollection>,
+    asteroid_spritesheets: Res<AsteroidTextureCollection>,
+    speed_ramp: Res<AsteroidSpeedRamp>,
+    playstate: Res<State<PlayState>>,
+    mut commands: Commands,
+) {
+    let event = trigger.event();
+    let playing_field = trigger.entity();
+    let mut rand = rand.single_mut();
+
+    let players = player_query
+        .iter()
+        .map(|player| player.0)
+        .collect::<Vec<_>>();
+    let in_play = asteroids_in_play(&asteroid_query);
+    debug!(count = event.count, "Spawning asteroid wave");
+    spawn_asteroids(
+        event.count,
+        &event.select_from,
+        event.entry,
+        &event.formation,
+        playing_field,
+        &players,
+        &in_play,
+        &player_settings,
+        &game_area_settings,
+        level_settings.asteroid_speed * **speed_ramp,
+        **playstate,
+        &asteroid_pool_collection,
+        &asteroid_spritesheets,
+        &mut rand,
+        &mut commands,
+    );
 }
 
 #[cfg(feature = "dbg_colliders")]
@@ -129,8 +176,10 @@ pub fn spawn_debug_asteroids(
                 None,
                 playing_field,
                 position,
+                "",
                 pool,
                 Some(pool_sheet_index),
+                1.0,
                 &asteroid_spritesheets,
                 &mut rand,
                 &mut commands,
@@ -142,11 +191,15 @@ pub fn spawn_debug_asteroids(
 pub fn on_asteroid_spawn_new(
     trigger: Trigger<AsteroidSpawnNewEvent>,
     mut rand: Query<&mut RngComponent, With<PlayingField>>,
+    level_settings: Option<Res<GameLevelSettings>>,
     mut commands: Commands,
 ) {
     let event = trigger.event();
     let playing_field = trigger.entity();
     let rand = RngComponent::from(&mut rand.single_mut());
+    let asteroid_physics = level_settings
+        .map(|level_settings| level_settings.asteroid_physics)
+        .unwrap_or_default();
 
     trace!(?event, "spawning new asteroid");
 
@@ -159,24 +212,51 @@ pub fn on_asteroid_spawn_new(
             },
             Name::new("Asteroid"),
             Asteroid,
-            RigidBody::Kinematic,
+            match asteroid_physics {
+                AsteroidPhysics::Kinematic => RigidBody::Kinematic,
+                AsteroidPhysics::Dynamic => RigidBody::Dynamic,
+            },
             Position::new(event.position.truncate()),
             event.linear_velocity,
             event.angular_velocity,
+            PoolKey::from(event.pool.clone()),
+            SurfaceMaterial::from(event.material),
+            (HitPoints::from(event.hit_points), PartialDamage::default()),
             HitBehavior::from(event.hit_behavior.clone()),
-            Wrapping,
+            DamageBehavior::from(event.damage_behavior.clone()),
+            BaseColor::from(event.spritesheet.color),
             rand,
         ));
+        if event.entering {
+            asteroid.insert(EnteringGameArea);
+        } else {
+            asteroid.insert(Wrapping);
+        }
+        if let Some(steering) = &event.steering {
+            asteroid.insert(Steering::new(steering.clone()));
+        }
         if event.playstate != Some(PlayState::Running) {
             asteroid.insert(PauseMovement);
         }
+        let collides_with = match asteroid_physics {
+            AsteroidPhysics::Kinematic => LayerMask::from([
+                CollisionLayer::Player,
+                CollisionLayer::Laser,
+                CollisionLayer::EnemyLaser,
+                CollisionLayer::Escort,
+            ]),
+            AsteroidPhysics::Dynamic => LayerMask::from([
+                CollisionLayer::Player,
+                CollisionLayer::Laser,
+                CollisionLayer::EnemyLaser,
+                CollisionLayer::Escort,
+                CollisionLayer::Asteroids,
+            ]),
+        };
         asteroid.insert_spritesheet(&event.spritesheet, Some(event.atlas_index), || {
             (
                 AsteroidSprite,
-                CollisionLayers::new(
-                    [CollisionLayer::Asteroids],
-                    [CollisionLayer::Player, CollisionLayer::Laser],
-                ),
+                CollisionLayers::new([CollisionLayer::Asteroids], collides_with),
             )
         });
     });
@@ -193,6 +273,11 @@ pub fn on_remove_asteroid(
     }
 }
 
+/// Run condition for replenishing the asteroids, once all are gone.
+pub fn no_asteroids_left(counter: Res<AsteroidCount>) -> bool {
+    **counter == 0
+}
+
 pub fn init_asteroid_counter(mut counter: ResMut<AsteroidCount>) {
     **counter = 0;
     trace!(asteroid_count = **counter);
@@ -213,17 +298,22 @@ pub fn on_asteroid_removed(
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn detect_asteroid_hits(
     mut projectile_hit_events: EventReader<ProjectileCollisionEvent>,
     collider_query: Query<&ColliderParent, With<AsteroidSprite>>,
     transform_query: Query<&Transform, With<Asteroid>>,
+    material_query: Query<&SurfaceMaterial>,
+    mut partial_damage_query: Query<&mut PartialDamage>,
+    player_query: Query<(), With<Player>>,
+    ammonition_depot: Res<AmmonitionDepot>,
     mut commands: Commands,
 ) {
     use itertools::Itertools;
 
-    for (asteroid, position, players) in projectile_hit_events
+    for (asteroid, position, players, direction, damage) in projectile_hit_events
         .read()
-        .into_group_map_by(|event| event.entity_hit)
+        .into_ordered_groups_by(|event| event.entity_hit)
         .into_iter()
         .filter_map(|(asteroid_sprite, hit_events_for_asteroid)| {
             collider_query.get(asteroid_sprite).ok().map(|asteroid| {
@@ -234,20 +324,64 @@ pub fn detect_asteroid_hits(
                     hit_events_for_asteroid
                         .iter()
                         .map(|collision_evt| collision_evt.shot_by_player)
+                        // enemies shoot asteroids as well, but don't score
+                        .filter(|shooter| player_query.contains(*shooter))
                         .unique()
                         .collect(),
+                    hit_events_for_asteroid
+                        .iter()
+                        .map(|collision_evt| collision_evt.direction)
+                        .sum::<Vec2>()
+                        .normalize_or_zero(),
+                    hit_events_for_asteroid
+                        .iter()
+                        .map(|collision_evt| {
+                            material_query
+                                .get(asteroid.get())
+                                .map(|material| {
+                                    ammonition_depot[&collision_evt.ammonition]
+                                        .effect_on(**material)
+                                })
+                                .unwrap_or_default()
+                                .damage(collision_evt.damage)
+                        })
+                        .sum::<f32>(),
                 )
             })
         })
     {
+        // reduced damage adds up until it takes a hit point
+        let damage = match partial_damage_query.get_mut(asteroid) {
+            Ok(mut partial_damage) => {
+                let total = **partial_damage + damage;
+                let full = (total + DAMAGE_TOLERANCE).floor();
+                **partial_damage = (total - full).max(0.0);
+                full as u32
+            }
+            Err(_) => damage.floor() as u32,
+        };
+        if damage == 0 {
+            trace!(?asteroid, "Asteroid hit without losing a hit point");
+            continue;
+        }
+
         debug!(
             ?asteroid,
             ?position,
             ?players,
+            damage,
             "Asteroid hit by a projectile"
         );
 
-        commands.trigger_targets(AsteroidHitEvent { position, players }, asteroid);
+        commands.trigger_targets(
+            AsteroidHitEvent {
+                position,
+                players,
+                direction,
+                damage,
+            },
+            asteroid,
+        );
     }
 }
 
@@ -255,11 +389,21 @@ pub fn detect_asteroid_hits(
 pub fn on_asteroid_hit(
     trigger: Trigger<AsteroidHitEvent>,
     mut playing_field: Query<(Entity, &mut RngComponent), With<PlayingField>>,
-    hit_behavior_query: Query<&HitBehavior>,
+    mut asteroid_query: Query<(
+        &mut HitPoints,
+        &HitBehavior,
+        &DamageBehavior,
+        &BaseColor,
+        &PoolKey,
+        Option<&LinearVelocity>,
+    )>,
     asteroid_pool_collection: Res<AsteroidPoolCollection>,
     asteroid_spritesheets: Res<AsteroidTextureCollection>,
     mut score_events: EventWriter<AddToScoreEvent>,
     mut remove_events: EventWriter<AsteroidRemoveEvent>,
+    mut destroyed_events: EventWriter<AsteroidDestroyedEvent>,
+    level_settings: Option<Res<GameLevelSettings>>,
+    speed_ramp: Res<AsteroidSpeedRamp>,
     mut commands: Commands,
     playstate: Res<State<PlayState>>,
     asset_server: Res<AssetServer>,
@@ -267,8 +411,32 @@ pub fn on_asteroid_hit(
     let hit_evt = trigger.event();
     let asteroid = trigger.entity();
     let (playing_field, mut rand) = playing_field.single_mut();
+    let Ok((mut hit_points, hit_behavior, damage_behavior, base_color, pool_key, parent_velocity)) =
+        asteroid_query.get_mut(asteroid)
+    else {
+        return;
+    };
+    let parent_velocity = parent_velocity.map_or(Vec2::ZERO, |velocity| velocity.0);
+    // children ramp up like the asteroids already in play
+    let asteroid_speed =
+        level_settings.map_or(1.0, |level_settings| level_settings.asteroid_speed) * **speed_ramp;
+    if **hit_points == 0 {
+        trace!(?asteroid, "Asteroid already destroyed");
+        return;
+    }
 
-    for hit_behavior in hit_behavior_query.get(asteroid).unwrap().iter() {
+    **hit_points = hit_points.saturating_sub(hit_evt.damage);
+    let behaviors = if **hit_points == 0 {
+        destroyed_events.send(AsteroidDestroyedEvent {
+            pool: (**pool_key).clone(),
+        });
+        hit_behavior.iter()
+    } else {
+        trace!(?asteroid, hit_points = **hit_points, "Asteroid damaged");
+        damage_behavior.iter()
+    };
+
+    for hit_behavior in behaviors {
         match hit_behavior {
             AsteroidHitBehavior::None => {}
 
@@ -276,12 +444,17 @@ pub fn on_asteroid_hit(
                 add_to_score(*points, &hit_evt.players, &mut score_events);
             }
 
-            AsteroidHitBehavior::Split { count, select_from } => {
+            AsteroidHitBehavior::Split {
+                count,
+                select_from,
+                velocity,
+            } => {
+                let mut children = Vec::new();
                 for _ in match count {
                     AsteroidSplitCount::Exact(max) => 0..*max,
                     AsteroidSplitCount::Range { start, end } => 0..rand.usize(start..end),
                 } {
-                    let Some(pool) =
+                    let Some((pool_key, pool)) =
                         select_from.pick_random_pool(&mut rand, &asteroid_pool_collection)
                     else {
                         warn!("did not find an asteroid pool");
@@ -303,17 +476,50 @@ pub fn on_asteroid_hit(
                         }
                     };
 
-                    spawn_asteroid_from_pool(
+                    children.extend(new_asteroid_from_pool(
                         GameState::Playing,
                         Some(**playstate),
-                        playing_field,
                         position,
+                        pool_key,
                         pool,
                         None,
+                        asteroid_speed,
                         &asteroid_spritesheets,
                         &mut rand,
-                        &mut commands,
-                    );
+                    ));
+                }
+
+                if let AsteroidSplitVelocity::Momentum {
+                    parent_factor,
+                    spread,
+                    conserve,
+                } = velocity
+                {
+                    for child in children.iter_mut() {
+                        let direction = if hit_evt.direction == Vec2::ZERO {
+                            Vec2::from_angle(rand.f32() * TAU)
+                        } else {
+                            Vec2::from_angle(rand.f32_normalized() * spread)
+                                .rotate(hit_evt.direction)
+                        };
+                        child.linear_velocity.0 = parent_velocity * *parent_factor
+                            + direction * child.linear_velocity.length();
+                    }
+
+                    if *conserve && !children.is_empty() {
+                        let mean_velocity = children
+                            .iter()
+                            .map(|child| child.linear_velocity.0)
+                            .sum::<Vec2>()
+                            / children.len() as f32;
+                        for child in children.iter_mut() {
+                            child.linear_velocity.0 += parent_velocity - mean_velocity;
+                        }
+                    }
+                }
+
+                for child in children {
+                    commands.trigger_targets(child, playing_field);
                 }
             }
 
@@ -337,6 +543,20 @@ pub fn on_asteroid_hit(
                     },
                 ));
             }
+
+            AsteroidHitBehavior::Flash { color, duration } => {
+                let tween = Tween::new(
+                    EaseFunction::QuadraticInOut,
+                    *duration / 2,
+                    SpriteColorLens {
+                        start: **base_color,
+                        end: *color,
+                    },
+                )
+                .with_repeat_strategy(RepeatStrategy::MirroredRepeat)
+                .with_repeat_count(RepeatCount::Finite(2));
+                commands.entity(asteroid).insert(Animator::new(tween));
+            }
         }
     }
 }
@@ -345,6 +565,9 @@ pub fn on_asteroid_hit(
 
 /// This is a doc comment
 fn add_to_score(score: usize, players: &[Entity], score_events: &mut EventWriter<AddToScoreEvent>) {
+    if players.is_empty() {
+        return;
+    }
     // and a normal comment
     let score = (score as f32 / players.len() as f32).ceil() as usize;
     for player in players.iter() {
@@ -355,25 +578,62 @@ fn add_to_score(score: usize, players: &[Entity], score_events: &mut EventWriter
     }
 }
 
+#[cfg(feature = "dbg_colliders")]
 #[allow(clippy::too_many_arguments)]
 fn spawn_asteroid_from_pool(
     state: GameState,
     playstate: Option<PlayState>,
     parent_entity: Entity,
     position: Vec3,
+    // Key of the pool in the [AsteroidPoolCollection]
+    pool_key: &str,
     pool: &AsteroidPool,
     // Inside the pool, which spritesheet index should be used
     pool_sheet_index: Option<usize>,
+    // Factor applied to the speed of the pool, see [GameLevelSettings::asteroid_speed]
+    speed_factor: f32,
     asteroid_spritesheets: &AsteroidTextureCollection,
     rand: &mut RngComponent,
     commands: &mut Commands,
 ) {
+    if let Some(event) = new_asteroid_from_pool(
+        state,
+        playstate,
+        position,
+        pool_key,
+        pool,
+        pool_sheet_index,
+        speed_factor,
+        asteroid_spritesheets,
+        rand,
+    ) {
+        commands.trigger_targets(event, parent_entity);
+    }
+}
+
+/// Creates the [AsteroidSpawnNewEvent] for a random asteroid from the pool, moving in a random
+/// direction.
+#[allow(clippy::too_many_arguments)]
+fn new_asteroid_from_pool(
+    state: GameState,
+    playstate: Option<PlayState>,
+    position: Vec3,
+    // Key of the pool in the [AsteroidPoolCollection]
+    pool_key: &str,
+    pool: &AsteroidPool,
+    // Inside the pool, which spritesheet index should be used
+    pool_sheet_index: Option<usize>,
+    // Factor applied to the speed of the pool, see [GameLevelSettings::asteroid_speed]
+    speed_factor: f32,
+    asteroid_spritesheets: &AsteroidTextureCollection,
+    rand: &mut RngComponent,
+) -> Option<AsteroidSpawnNewEvent> {
     let (spritesheet, atlas_index, speed_range, rotation_range) = {
         let pool_sheet_index =
             pool_sheet_index.unwrap_or_else(|| rand.usize(0..pool.textures.len()));
         let Some(texture_selection) = pool.textures.get(pool_sheet_index) else {
             warn!(pool_sheet_index, "Could not find sprite sheet");
-            return;
+            return None;
         };
         match texture_selection {
             AsteroidTextureSelection::AtlasIndex {
@@ -384,7 +644,7 @@ fn spawn_asteroid_from_pool(
             } => {
                 let Some(spritesheet) = asteroid_spritesheets.get(spritesheet_key) else {
                     warn!(spritesheet_key, "Could not find sprite sheet");
-                    return;
+                    return None;
                 };
 
                 let index = index.unwrap_or(spritesheet.atlas_index);
@@ -414,8 +674,9 @@ fn spawn_asteroid_from_pool(
                 AsteroidSpeedRange::None => 0.0,
                 AsteroidSpeedRange::Exact(units_per_second) => units_per_second as f32,
                 AsteroidSpeedRange::Range { start, end } => rand.f32_range(start..end),
-            })
-        .truncate(),
+            }
+            * speed_factor)
+            .truncate(),
     );
     let angular_velocity = match rotation_range {
         crate::assets::AsteroidRotationSpeed::None => AngularVelocity::ZERO,
@@ -426,20 +687,175 @@ fn spawn_asteroid_from_pool(
     };
 
     let hit_behavior = pool.hit_behavior.clone();
+    let damage_behavior = pool.damage_behavior.clone();
+
+    Some(AsteroidSpawnNewEvent {
+        state,
+        playstate,
+        position,
+        entering: false,
+        linear_velocity,
+        angular_velocity,
+        pool: pool_key.to_string(),
+        steering: pool.steering.clone(),
+        material: pool.material,
+        hit_points: pool.hit_points,
+        hit_behavior,
+        damage_behavior,
+        spritesheet,
+        atlas_index,
+    })
+}
 
-    commands.trigger_targets(
-        AsteroidSpawnNewEvent {
-            state,
-            playstate,
-            position,
-            linear_velocity,
-            angular_velocity,
-            hit_behavior,
-            spritesheet,
-            atlas_index,
-        },
-        parent_entity,
+/// Spawns asteroids picked from the pools, placed as given by the [AsteroidEntry] and the
+/// [AsteroidFormation].
+#[allow(clippy::too_many_arguments)]
+fn spawn_asteroids(
+    count: usize,
+    select_from: &[AsteroidSelection],
+    entry: AsteroidEntry,
+    formation: &AsteroidFormation,
+    playing_field: Entity,
+    players: &[Vec2],
+    // Bounding circles of the asteroids already in play, see [asteroids_in_play]
+    in_play: &[(Vec2, f32)],
+    player_settings: &PlayerSettings,
+    game_area_settings: &GameAreaSettings,
+    // Factor applied to the speed of the pool, see [GameLevelSettings::asteroid_speed]
+    speed_factor: f32,
+    playstate: PlayState,
+    asteroid_pool_collection: &AsteroidPoolCollection,
+    asteroid_spritesheets: &AsteroidTextureCollection,
+    rand: &mut RngComponent,
+    commands: &mut Commands,
+) {
+    // entering asteroids come in from the edges, ignoring the formation
+    let formation_used =
+        entry == AsteroidEntry::Inside && formation.layout != AsteroidLayout::Random;
+    let formation = formation_used.then(|| FormationPlacement::new(formation, count, rand));
+    // players may have moved away from the spawn position already
+    let mut occupied = OccupiedAreas::keep_free(
+        players
+            .iter()
+            .copied()
+            .chain([player_settings.spawn_position]),
+        player_settings.safe_radius,
     );
+    for (position, radius) in in_play.iter() {
+        occupied.occupy(*position, *radius);
+    }
+    let mut spawned = 0;
+
+    for index in 0..count {
+        let Some((pool_key, pool)) = select_from.pick_random_pool(rand, asteroid_pool_collection)
+        else {
+            warn!("did not find an asteroid pool");
+            continue;
+        };
+        let Some(mut event) = new_asteroid_from_pool(
+            GameState::Playing,
+            Some(playstate),
+            Vec3::ZERO,
+            pool_key,
+            pool,
+            None,
+            speed_factor,
+            asteroid_spritesheets,
+            rand,
+        ) else {
+            continue;
+        };
+
+        match entry {
+            AsteroidEntry::Inside => {
+                let radius = event.spritesheet.bounding_radius();
+                let position = match &formation {
+                    Some(formation) => formation.place(index, radius, &mut occupied, rand),
+                    None => random_spawn_position(
+                        player_settings,
+                        &game_area_settings.game_area,
+                        radius,
+                        &mut occupied,
+                        rand,
+                    ),
+                };
+                let Some(position) = position else {
+                    continue;
+                };
+                event.position = position.extend(-rand.f32_range(ASTEROID_Z_RANGE));
+                if let Some(formation) = &formation {
+                    event.linear_velocity = formation.velocity(position, event.linear_velocity);
+                }
+                commands.trigger_targets(event, playing_field);
+            }
+            AsteroidEntry::Edges { warning } => {
+                spawn_entry_telegraph(
+                    event,
+                    warning,
+                    game_area_settings,
+                    playing_field,
+                    rand,
+                    commands,
+                );
+            }
+        }
+        spawned += 1;
+    }
+
+    if spawned < count {
+        warn!(
+            count,
+            spawned,
+            in_play = in_play.len(),
+            "Could not fit all asteroids into the game area"
+        );
+    }
+}
+
+/// Bounding circles of the asteroid colliders, which new asteroids must not overlap.
+fn asteroids_in_play(
+    asteroid_query: &Query<&ColliderAabb, With<AsteroidSprite>>,
+) -> Vec<(Vec2, f32)> {
+    asteroid_query
+        .iter()
+        .map(|aabb| {
+            (
+                (aabb.min + aabb.max) / 2.0,
+                (aabb.max - aabb.min).length() / 2.0,
+            )
+        })
+        .collect()
+}
+
+/// Picks a random position inside the game area, outside the safe radius around the player spawn
+/// position, where an asteroid with the collider radius keeps its distance to the
+/// [OccupiedAreas].
+///
+/// Gives up after [MAX_SPAWN_ATTEMPTS], when the game area is too crowded.
+fn random_spawn_position(
+    player_settings: &PlayerSettings,
+    game_area: &GameArea,
+    radius: f32,
+    occupied: &mut OccupiedAreas,
+    rand: &mut RngComponent,
+) -> Option<Vec2> {
+    let player_pos = player_settings.spawn_position;
+    let min_distance = player_settings.safe_radius;
+
+    for _ in 0..MAX_SPAWN_ATTEMPTS {
+        let angle = rand.f32_range(0.0..TAU);
+        let intersection = calc_intersection(player_pos, angle, game_area);
+        let max_distance = player_pos.distance(intersection);
+        if max_distance > min_distance {
+            let distance = rand.f32_range(min_distance..max_distance);
+            let position = Vec2::from_angle(angle) * distance;
+            if occupied.is_free(position, radius) {
+                occupied.occupy(position, radius);
+                return Some(position);
+            }
+        }
+    }
+    None
 }
 
 /// Given an area, a position and a direction, calculate the intersection point with the edge of the area.
@@ -50,13 +50,20 @@ fn set_state_background(
         GameState::LoadingAssets => None,
         GameState::MainMenu => Some(backgrounds.main_menu.clone()),
         GameState::HighscoreMenu => Some(backgrounds.highscores_menu.clone()),
+        #[cfg(feature = "cmd_line")]
+        GameState::Connecting => Some(backgrounds.main_menu.clone()),
         GameState::Playing => match **play_state.unwrap() {
             PlayState::CountdownBeforeRunning => level_settings
                 .map(|s| s.background.clone())
                 .map(|h| asset_server.load(h)),
             PlayState::GameOver(reason) => match reason {
-                GameOverReason::PlayerDead => Some(backgrounds.game_over.clone()),
-                GameOverReason::GameWon => Some(backgrounds.game_won.clone()),
+                GameOverReason::PlayerDead
+                | GameOverReason::TimeUp
+                | GameOverReason::EscortLost
+                | GameOverReason::Disconnected => Some(backgrounds.game_over.clone()),
+                GameOverReason::GameWon | GameOverReason::MatchWon => {
+                    Some(backgrounds.game_won.clone())
+                }
             },
             _ => {
                 // Do NOT change background for any other state.
@@ -1,9 +1,16 @@
+use std::net::SocketAddr;
+
 use bevy::prelude::*;
+use bevy_turborand::{DelegatedRng, GlobalRng};
 use clap::Parser;
 #[allow(unused)]
 use tracing::*;
 
-use crate::{assets::GameStartSettings, states::GameState};
+use crate::{
+    assets::{Difficulty, GameMode, GameStartSettings},
+    network::{NetworkMode, NetworkPlugin, NetworkRole},
+    states::GameState,
+};
 
 #[derive(Parser, Debug, Resource)]
 #[clap(author, version, about, long_about = None)]
@@ -13,6 +20,34 @@ pub struct Args {
 
     #[clap(long)]
     pub level: Option<String>,
+
+    /// number of local players
+    #[clap(long, value_parser = clap::value_parser!(u8).range(1..=GameStartSettings::MAX_PLAYERS as i64))]
+    pub players: Option<u8>,
+
+    #[clap(long, value_enum)]
+    pub difficulty: Option<Difficulty>,
+
+    /// game mode, also of a hosted network game
+    #[clap(long, value_enum)]
+    pub mode: Option<GameMode>,
+
+    /// seed for all random numbers, making a game reproducible
+    #[clap(long)]
+    pub seed: Option<u64>,
+
+    /// host a network game for two players, waiting for the other one at this address
+    #[clap(long, conflicts_with_all = ["connect", "players", "play"])]
+    pub host: Option<SocketAddr>,
+
+    /// wait for the inputs of both players every tick instead of predicting them, for
+    /// debugging a hosted network game
+    #[clap(long, requires = "host")]
+    pub lockstep: bool,
+
+    /// join the network game hosted at this address
+    #[clap(long, conflicts_with_all = ["players", "seed", "play"])]
+    pub connect: Option<SocketAddr>,
 }
 
 pub struct CmdLinePlugin;
@@ -22,10 +57,30 @@ pub struct CmdLineSet;
 
 impl Plugin for CmdLinePlugin {
     fn build(&self, app: &mut App) {
-        app.insert_resource(<Args as clap::Parser>::parse())
+        let args = <Args as clap::Parser>::parse();
+        if let Some(seed) = args.seed {
+            debug!(seed, "seeding random numbers");
+            app.insert_resource(GlobalRng::with_seed(seed));
+        }
+        if let Some(bind) = args.host {
+            let seed = args.seed.unwrap_or_else(|| GlobalRng::new().u64(..));
+            let mode = match args.lockstep {
+                true => NetworkMode::Lockstep,
+                false => NetworkMode::Rollback,
+            };
+            app.add_plugins(NetworkPlugin {
+                role: NetworkRole::Host { bind, seed, mode },
+            });
+        } else if let Some(host) = args.connect {
+            app.add_plugins(NetworkPlugin {
+                role: NetworkRole::Client { host },
+            });
+        }
+
+        app.insert_resource(args)
             .add_systems(
                 OnExit(GameState::LoadingAssets),
-                set_start_level.in_set(CmdLineSet),
+                (set_start_level, set_players, set_difficulty, set_mode).in_set(CmdLineSet),
             )
             .add_systems(
                 OnEnter(GameState::MainMenu),
@@ -41,6 +96,27 @@ fn set_start_level(args: Res<Args>, mut game_start: ResMut<GameStartSettings>) {
     }
 }
 
+fn set_players(args: Res<Args>, mut game_start: ResMut<GameStartSettings>) {
+    if let Some(players) = args.players {
+        debug!(players, "set number of players");
+        game_start.players = players as usize;
+    }
+}
+
+fn set_difficulty(args: Res<Args>, mut game_start: ResMut<GameStartSettings>) {
+    if let Some(difficulty) = args.difficulty {
+        debug!(?difficulty, "set difficulty");
+        game_start.difficulty = difficulty;
+    }
+}
+
+fn set_mode(args: Res<Args>, mut game_start: ResMut<GameStartSettings>) {
+    if let Some(mode) = args.mode {
+        debug!(?mode, "set game mode");
+        game_start.mode = mode;
+    }
+}
+
 fn start_play(args: Res<Args>, mut next: ResMut<NextState<GameState>>) {
     if args.play {
         debug!("starting game directly");
@@ -8,5 +8,14 @@ pub const PLAYINGFIELD_BACKGROUND_RELATIVE_Z_POS: f32 = -100.0;
 
 pub const PLAYER_Z_POS: f32 = 0.0;
 pub const ASTEROID_Z_RANGE: Range<f32> = 10.0..20.0;
+pub const BEAM_RELATIVE_Z_POS: f32 = -1.0;
+pub const SHIELD_RELATIVE_Z_POS: f32 = 0.5;
+pub const JUMP_RETICLE_Z_POS: f32 = 30.0;
+pub const ENEMY_Z_POS: f32 = -5.0;
+pub const ESCORT_Z_POS: f32 = -3.0;
+pub const ENTRY_TELEGRAPH_Z_POS: f32 = 5.0;
 
 pub const AUDIO_SCALE: f32 = 1. / 250.0;
+
+/// Ticks per second of the gameplay, which runs in the fixed schedules, one physics step per tick.
+pub const TICKS_PER_SECOND: f64 = 60.0;
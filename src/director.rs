@@ -0,0 +1,145 @@
+//! Module for the level director, executing the [GameLevelSettings::timeline] of a level.
+
+use core::time::Duration;
+
+use bevy::prelude::*;
+use itertools::Itertools;
+use smart_default::SmartDefault;
+#[allow(unused)]
+use tracing::*;
+
+use crate::{
+    assets::{GameLevelSettings, TimelineAction, TimelineEvent, TimelineTrigger},
+    asteroid::{AsteroidCount, AsteroidSpawnWaveEvent},
+    enemy::EnemySpawnEvent,
+    levels::{GameLevelsSet, LevelTimer},
+    rollback::RollbackApp,
+    states::PlayState,
+    PlayingField,
+};
+
+/// Events of the [GameLevelSettings::timeline] still to happen in the current level.
+#[derive(Resource, Debug, Default, Clone, Deref, DerefMut)]
+pub struct LevelTimeline(Vec<ScheduledTimelineEvent>);
+
+#[derive(Debug, Clone)]
+pub struct ScheduledTimelineEvent {
+    pub event: TimelineEvent,
+    /// the level time before which the event can't happen, moved on by repeating events
+    pub not_before: Duration,
+}
+
+/// Plugin executing the timeline of the current level.
+#[derive(SmartDefault, Debug)]
+pub struct LevelDirectorPlugin;
+
+/// All systems added by the [LevelDirectorPlugin] plugin belongs to this set.
+#[derive(Debug, Clone, Default, SystemSet, PartialEq, Eq, Hash)]
+pub struct LevelDirectorSet;
+
+impl Plugin for LevelDirectorPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<LevelTimeline>()
+            .rollback_resource::<LevelTimeline>()
+            .add_systems(
+                OnEnter(PlayState::StartNewGame),
+                init_level_timeline
+                    .in_set(LevelDirectorSet)
+                    .after(GameLevelsSet),
+            )
+            .add_systems(
+                OnExit(PlayState::StartNextLevel),
+                init_level_timeline
+                    .in_set(LevelDirectorSet)
+                    .after(GameLevelsSet),
+            )
+            .add_systems(
+                FixedUpdate,
+                // the level timer only runs in PlayState::Running, so the timeline pauses with
+                // the game
+                run_level_timeline
+                    .after(GameLevelsSet)
+                    .run_if(in_state(PlayState::Running))
+                    .in_set(LevelDirectorSet),
+            );
+    }
+}
+
+fn init_level_timeline(level_settings: Res<GameLevelSettings>, mut commands: Commands) {
+    let timeline = level_settings
+        .timeline
+        .iter()
+        .map(|event| ScheduledTimelineEvent {
+            event: event.clone(),
+            not_before: Duration::ZERO,
+        })
+        .collect_vec();
+    debug!(events = timeline.len(), "Scheduling level timeline");
+    commands.insert_resource(LevelTimeline(timeline));
+}
+
+fn run_level_timeline(
+    level_timer: Res<LevelTimer>,
+    asteroid_counter: Res<AsteroidCount>,
+    mut timeline: ResMut<LevelTimeline>,
+    playing_field: Query<Entity, With<PlayingField>>,
+    asset_server: Res<AssetServer>,
+    mut commands: Commands,
+) {
+    let elapsed = level_timer.elapsed();
+    let playing_field = playing_field.single();
+    timeline.retain_mut(|scheduled| {
+        let triggered = elapsed >= scheduled.not_before
+            && match scheduled.event.when {
+                TimelineTrigger::At(time) => elapsed >= time,
+                TimelineTrigger::AsteroidsBelow(count) => **asteroid_counter < count,
+            };
+        if !triggered {
+            return true;
+        }
+
+        debug!(?elapsed, when = ?scheduled.event.when, "Timeline event happening");
+        for action in scheduled.event.actions.iter() {
+            match action {
+                TimelineAction::SpawnAsteroids {
+                    count,
+                    select_from,
+                    entry,
+                    formation,
+                } => {
+                    commands.trigger_targets(
+                        AsteroidSpawnWaveEvent {
+                            count: *count,
+                            select_from: select_from.clone(),
+                            entry: *entry,
+                            formation: formation.clone(),
+                        },
+                        playing_field,
+                    );
+                }
+                TimelineAction::SpawnEnemy(enemy) => {
+                    commands.trigger_targets(
+                        EnemySpawnEvent {
+                            enemy: enemy.clone(),
+                        },
+                        playing_field,
+                    );
+                }
+                TimelineAction::PlayAudio(audio) => {
+                    commands.spawn(AudioBundle {
+                        source: asset_server.load(audio),
+                        settings: PlaybackSettings::DESPAWN,
+                    });
+                }
+            }
+        }
+
+        match scheduled.event.repeat {
+            Some(repeat) => {
+                scheduled.not_before = elapsed + repeat;
+                true
+            }
+            None => false,
+        }
+    });
+}
@@ -0,0 +1,17 @@
+use bevy::prelude::*;
+use derive_more::derive::{Deref, DerefMut, From};
+
+/// An enemy ship, described by the [crate::assets::EnemyInfo] with the given key.
+#[derive(Component, Debug, Clone, Deref, From)]
+pub struct Enemy(String);
+
+#[derive(Component, Debug, Clone, Copy)]
+pub struct EnemySprite;
+
+/// Remaining hit points of an enemy.
+#[derive(Component, Debug, Clone, Copy, Deref, DerefMut, From)]
+pub struct EnemyHitPoints(u32);
+
+/// Time until the enemy fires its weapon again.
+#[derive(Component, Debug, Clone, Deref, DerefMut, From)]
+pub struct EnemyWeaponTimer(Timer);
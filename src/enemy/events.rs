@@ -0,0 +1,14 @@
+use bevy::prelude::*;
+
+/// Triggered on the [crate::PlayingField] to spawn the enemy with the given key.
+#[derive(Event, Debug, Clone)]
+pub struct EnemySpawnEvent {
+    pub enemy: String,
+}
+
+#[derive(Event, Debug, Clone)]
+pub struct EnemyHitEvent {
+    pub players: Vec<Entity>,
+    /// hit points to remove from the enemy
+    pub damage: u32,
+}
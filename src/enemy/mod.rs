@@ -0,0 +1,9 @@
+mod components;
+mod events;
+mod plugin;
+mod resources;
+mod systems;
+
+use self::systems::*;
+
+pub use self::{components::*, events::*, plugin::*, resources::*};
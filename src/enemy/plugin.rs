@@ -0,0 +1,65 @@
+use bevy::prelude::*;
+use smart_default::SmartDefault;
+
+use crate::{
+    enemy::{
+        despawn_all_enemies, detect_enemy_hits, fire_enemy_weapons, hunt_players,
+        init_enemy_schedule, on_enemy_hit, on_enemy_spawn, spawn_scheduled_enemies, Enemy,
+        EnemyHitPoints, EnemySchedule, EnemySprite, EnemyWeaponTimer,
+    },
+    levels::GameLevelsSet,
+    projectile::ProjectileSet,
+    rollback::RollbackApp,
+    PlayState,
+};
+
+#[derive(Debug, SystemSet, PartialEq, Eq, Hash, Clone)]
+pub struct EnemySet;
+
+#[derive(SmartDefault, Debug)]
+pub struct EnemyPlugin;
+
+impl Plugin for EnemyPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<EnemySchedule>()
+            .rollback_resource::<EnemySchedule>()
+            .rollback_component::<Enemy>()
+            .rollback_component::<EnemySprite>()
+            .rollback_component::<EnemyHitPoints>()
+            .rollback_component::<EnemyWeaponTimer>();
+
+        app.add_systems(
+            OnEnter(PlayState::StartNewGame),
+            (despawn_all_enemies, init_enemy_schedule)
+                .chain()
+                .in_set(EnemySet)
+                .after(GameLevelsSet),
+        )
+        .add_systems(
+            OnExit(PlayState::StartNextLevel),
+            (despawn_all_enemies, init_enemy_schedule)
+                .chain()
+                .in_set(EnemySet)
+                .after(GameLevelsSet),
+        )
+        .add_systems(
+            OnExit(PlayState::StartAfterDeath),
+            despawn_all_enemies.in_set(EnemySet),
+        )
+        .add_systems(
+            FixedUpdate,
+            (spawn_scheduled_enemies, hunt_players, fire_enemy_weapons)
+                .run_if(in_state(PlayState::Running))
+                .in_set(EnemySet),
+        )
+        .add_systems(
+            FixedPostUpdate,
+            detect_enemy_hits
+                .run_if(in_state(PlayState::Running))
+                .in_set(EnemySet),
+        )
+        .configure_sets(FixedPostUpdate, EnemySet.after(ProjectileSet));
+
+        app.observe(on_enemy_spawn).observe(on_enemy_hit);
+    }
+}
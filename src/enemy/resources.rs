@@ -0,0 +1,13 @@
+use bevy::prelude::*;
+use derive_more::{Constructor, Debug, Deref, DerefMut};
+
+/// Enemies still to appear in the current level.
+#[derive(Resource, Debug, Default, Clone, Constructor, Deref, DerefMut)]
+pub struct EnemySchedule(Vec<ScheduledEnemy>);
+
+#[derive(Debug, Clone)]
+pub struct ScheduledEnemy {
+    pub enemy: String,
+    pub timer: Timer,
+    pub repeat: Option<core::time::Duration>,
+}
@@ -0,0 +1,333 @@
+use core::f32::consts::{PI, TAU};
+
+use avian2d::prelude::*;
+use bevy::{audio::Volume, prelude::*};
+use bevy_turborand::{DelegatedRng, RngComponent};
+use itertools::Itertools;
+
+use crate::{
+    assets::{
+        EnemyAim, EnemyCollection, EnemyHitBehavior, EnemyMovement, EnemyTextureCollection,
+        EntitySpriteSheetCommands, GameAreaSettings, GameLevelSettings,
+    },
+    constants::ENEMY_Z_POS,
+    enemy::{
+        Enemy, EnemyHitEvent, EnemyHitPoints, EnemySchedule, EnemySpawnEvent, EnemySprite,
+        EnemyWeaponTimer, ScheduledEnemy,
+    },
+    movement::{Steering, Wrapping},
+    player::{AddToScoreEvent, Dead, Player},
+    projectile::{ProjectileCollisionEvent, SpawnProjectilesEvent},
+    utils::IntoOrderedGroups,
+    CollisionLayer, GameState, PlayingField,
+};
+
+pub fn despawn_all_enemies(mut commands: Commands, query: Query<Entity, With<Enemy>>) {
+    for enemy in query.iter() {
+        trace!(?enemy, "Despawning enemy");
+        commands.entity(enemy).despawn_recursive();
+    }
+}
+
+/// Schedules the enemies of the level that is about to start.
+pub fn init_enemy_schedule(level_settings: Res<GameLevelSettings>, mut commands: Commands) {
+    let schedule = level_settings
+        .enemies
+        .iter()
+        .map(|appearance| ScheduledEnemy {
+            enemy: appearance.enemy.clone(),
+            timer: Timer::new(appearance.delay, TimerMode::Once),
+            repeat: appearance.repeat,
+        })
+        .collect_vec();
+    debug!(enemies = schedule.len(), "Scheduling level enemies");
+    commands.insert_resource(EnemySchedule::new(schedule));
+}
+
+pub fn spawn_scheduled_enemies(
+    time: Res<Time>,
+    mut schedule: ResMut<EnemySchedule>,
+    playing_field: Query<Entity, With<PlayingField>>,
+    mut commands: Commands,
+) {
+    let playing_field = playing_field.single();
+    schedule.retain_mut(|scheduled| {
+        if !scheduled.timer.tick(time.delta()).just_finished() {
+            return true;
+        }
+
+        commands.trigger_targets(
+            EnemySpawnEvent {
+                enemy: scheduled.enemy.clone(),
+            },
+            playing_field,
+        );
+
+        match scheduled.repeat {
+            Some(repeat) => {
+                scheduled.timer = Timer::new(repeat, TimerMode::Once);
+                true
+            }
+            None => false,
+        }
+    });
+}
+
+/// Turns hunting enemies towards the closest player.
+pub fn hunt_players(
+    time: Res<Time>,
+    enemy_collection: Res<EnemyCollection>,
+    mut enemy_query: Query<(&Enemy, &Position, &mut LinearVelocity, &mut Rotation)>,
+    player_query: Query<&Position, (With<Player>, Without<Dead>)>,
+) {
+    for (enemy, position, mut velocity, mut rotation) in enemy_query.iter_mut() {
+        let EnemyMovement::Hunt { speed, turn_rate } = enemy_collection[&**enemy].movement else {
+            continue;
+        };
+        let Some(target) = closest_player(position.0, player_query.iter()) else {
+            continue;
+        };
+
+        let heading = rotation.as_radians();
+        let delta = target - position.0;
+        let turn = (delta.y.atan2(delta.x) - heading + PI).rem_euclid(TAU) - PI;
+        let max_turn = turn_rate * time.delta_seconds();
+
+        *rotation = Rotation::radians(heading + turn.clamp(-max_turn, max_turn));
+        velocity.0 = *rotation * Vec2::X * speed;
+    }
+}
+
+pub fn fire_enemy_weapons(
+    time: Res<Time>,
+    enemy_collection: Res<EnemyCollection>,
+    mut enemy_query: Query<(Entity, &Enemy, &Position, &mut EnemyWeaponTimer)>,
+    player_query: Query<&Position, (With<Player>, Without<Dead>)>,
+    mut projectile_events: EventWriter<SpawnProjectilesEvent>,
+) {
+    for (entity, enemy, position, mut timer) in enemy_query.iter_mut() {
+        if !timer.tick(time.delta()).just_finished() {
+            continue;
+        }
+        let Some(weapon) = &enemy_collection[&**enemy].weapon else {
+            continue;
+        };
+
+        let direction = match weapon.aim {
+            EnemyAim::Forward => None,
+            EnemyAim::Player => {
+                let Some(target) = closest_player(position.0, player_query.iter()) else {
+                    continue;
+                };
+                let delta = target - position.0;
+                Some(Rotation::radians(delta.y.atan2(delta.x)))
+            }
+        };
+
+        let filters = if weapon.hits_asteroids {
+            LayerMask::from([
+                CollisionLayer::Player,
+                CollisionLayer::Escort,
+                CollisionLayer::Asteroids,
+            ])
+        } else {
+            LayerMask::from([CollisionLayer::Player, CollisionLayer::Escort])
+        };
+
+        trace!(enemy = ?entity, "Enemy firing");
+        projectile_events.send(SpawnProjectilesEvent {
+            shooter: entity,
+            weapon: weapon.weapon_key.clone(),
+            layers: CollisionLayers::new([CollisionLayer::EnemyLaser], filters),
+            direction,
+        });
+    }
+}
+
+pub fn detect_enemy_hits(
+    mut projectile_hit_events: EventReader<ProjectileCollisionEvent>,
+    collider_query: Query<&ColliderParent, With<EnemySprite>>,
+    mut commands: Commands,
+) {
+    for (enemy, hit_events_for_enemy) in projectile_hit_events
+        .read()
+        .filter_map(|event| {
+            collider_query
+                .get(event.entity_hit)
+                .ok()
+                .map(|enemy| (enemy.get(), event))
+        })
+        .into_ordered_groups()
+    {
+        let players = hit_events_for_enemy
+            .iter()
+            .map(|collision_evt| collision_evt.shot_by_player)
+            .unique()
+            .collect_vec();
+        let damage = hit_events_for_enemy
+            .iter()
+            .map(|collision_evt| collision_evt.damage)
+            .sum::<u32>();
+
+        debug!(?enemy, ?players, damage, "Enemy hit by a projectile");
+        commands.trigger_targets(EnemyHitEvent { players, damage }, enemy);
+    }
+}
+
+// region: observed events
+
+pub fn on_enemy_spawn(
+    trigger: Trigger<EnemySpawnEvent>,
+    mut rand: Query<&mut RngComponent, With<PlayingField>>,
+    enemy_collection: Res<EnemyCollection>,
+    enemy_spritesheets: Res<EnemyTextureCollection>,
+    game_area_settings: Res<GameAreaSettings>,
+    mut commands: Commands,
+) {
+    let event = trigger.event();
+    let playing_field = trigger.entity();
+
+    let Some(enemy_info) = enemy_collection.get(&event.enemy) else {
+        warn!(enemy = event.enemy, "Could not find enemy");
+        return;
+    };
+    let Some(spritesheet) = enemy_spritesheets.get(&enemy_info.texture_key) else {
+        warn!(
+            texture_key = enemy_info.texture_key,
+            "Could not find sprite sheet"
+        );
+        return;
+    };
+    let mut rand = RngComponent::from(&mut rand.single_mut());
+
+    // enemies enter the game at a random position on its edge
+    let position = {
+        let game_area = &game_area_settings.game_area;
+        let (min, max) = (game_area.min(), game_area.max());
+        let t = rand.f32();
+        match rand.usize(0..4) {
+            0 => Vec2::new(min.x.lerp(max.x, t), min.y),
+            1 => Vec2::new(min.x.lerp(max.x, t), max.y),
+            2 => Vec2::new(min.x, min.y.lerp(max.y, t)),
+            _ => Vec2::new(max.x, min.y.lerp(max.y, t)),
+        }
+    };
+    let rotation = Rotation::radians(rand.f32() * TAU);
+    let speed = match &enemy_info.movement {
+        EnemyMovement::Drift { speed } | EnemyMovement::Hunt { speed, .. } => *speed,
+        EnemyMovement::Steer(steering) => steering.max_speed,
+    };
+
+    debug!(enemy = event.enemy, ?position, "Spawning enemy");
+    commands.entity(playing_field).with_children(|commands| {
+        let mut enemy = commands.spawn((
+            StateScoped(GameState::Playing),
+            SpatialBundle {
+                transform: Transform::from_translation(position.extend(ENEMY_Z_POS)),
+                ..default()
+            },
+            Name::new("Enemy"),
+            Enemy::from(event.enemy.clone()),
+            EnemyHitPoints::from(enemy_info.hit_points),
+            RigidBody::Kinematic,
+            Position::new(position),
+            rotation,
+            LinearVelocity(rotation * Vec2::X * speed),
+            Wrapping,
+            rand,
+        ));
+        if let EnemyMovement::Steer(steering) = &enemy_info.movement {
+            enemy.insert(Steering::new(steering.clone()));
+        }
+        if let Some(weapon) = &enemy_info.weapon {
+            enemy.insert(EnemyWeaponTimer::from(Timer::new(
+                weapon.interval,
+                TimerMode::Repeating,
+            )));
+        }
+        enemy.insert_spritesheet(spritesheet, None, || {
+            (
+                EnemySprite,
+                Sensor,
+                CollisionLayers::new(
+                    [CollisionLayer::Enemy],
+                    [CollisionLayer::Player, CollisionLayer::Laser],
+                ),
+            )
+        });
+    });
+}
+
+pub fn on_enemy_hit(
+    trigger: Trigger<EnemyHitEvent>,
+    mut enemy_query: Query<(&Enemy, &mut EnemyHitPoints, &Transform)>,
+    enemy_collection: Res<EnemyCollection>,
+    mut score_events: EventWriter<AddToScoreEvent>,
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+) {
+    let hit_evt = trigger.event();
+    let enemy = trigger.entity();
+    let Ok((enemy_key, mut hit_points, transform)) = enemy_query.get_mut(enemy) else {
+        return;
+    };
+    if **hit_points == 0 {
+        trace!(?enemy, "Enemy already destroyed");
+        return;
+    }
+
+    **hit_points = hit_points.saturating_sub(hit_evt.damage);
+    if **hit_points > 0 {
+        trace!(?enemy, hit_points = **hit_points, "Enemy damaged");
+        return;
+    }
+
+    let enemy_info = &enemy_collection[&**enemy_key];
+    let score = (enemy_info.score as f32 / hit_evt.players.len() as f32).ceil() as usize;
+    for player in hit_evt.players.iter() {
+        score_events.send(AddToScoreEvent {
+            player: *player,
+            score,
+        });
+    }
+
+    for hit_behavior in enemy_info.hit_behavior.iter() {
+        match hit_behavior {
+            EnemyHitBehavior::None => {}
+
+            EnemyHitBehavior::Audio(audio) => {
+                commands.spawn((
+                    SpatialBundle {
+                        transform: Transform::from_translation(transform.translation),
+                        ..default()
+                    },
+                    AudioBundle {
+                        source: asset_server.load(audio),
+                        settings: PlaybackSettings {
+                            volume: Volume::new(1.0),
+                            spatial: true,
+                            ..PlaybackSettings::DESPAWN
+                        },
+                    },
+                ));
+            }
+
+            EnemyHitBehavior::Despawn => {
+                commands.entity(enemy).despawn_recursive();
+            }
+        }
+    }
+}
+
+// endregion
+
+// region: general functions
+
+fn closest_player<'a>(position: Vec2, players: impl Iterator<Item = &'a Position>) -> Option<Vec2> {
+    players.map(|player| player.0).min_by(|a, b| {
+        a.distance_squared(position)
+            .total_cmp(&b.distance_squared(position))
+    })
+}
+
+// endregion
@@ -0,0 +1,183 @@
+//! Module for the escort ship of levels with a [LevelObjective::Escort] objective.
+
+use avian2d::prelude::*;
+use bevy::prelude::*;
+use derive_more::{Deref, DerefMut, From};
+use smart_default::SmartDefault;
+#[allow(unused)]
+use tracing::*;
+
+use crate::{
+    assets::{
+        EntitySpriteSheetCommands, GameLevelSettings, LevelObjective, PlayerTextureCollection,
+    },
+    asteroid::AsteroidSprite,
+    constants::ESCORT_Z_POS,
+    levels::GameLevelsSet,
+    movement::{PauseMovement, Wrapping},
+    projectile::{ProjectileCollisionEvent, ProjectileSet},
+    rollback::RollbackApp,
+    states::{GameOverReason, GameState, PlayState},
+    CollisionLayer, PlayingField,
+};
+
+/// The ship the players have to protect.
+#[derive(Component, Debug, Clone, Copy)]
+pub struct Escort;
+
+#[derive(Component, Debug, Clone, Copy)]
+pub struct EscortSprite;
+
+/// Remaining hit points of the escort.
+#[derive(Component, Debug, Clone, Copy, Deref, DerefMut, From)]
+pub struct EscortHitPoints(u32);
+
+/// Plugin spawning the escort and ending the game when it is destroyed.
+#[derive(SmartDefault, Debug)]
+pub struct EscortPlugin;
+
+/// All systems added by the [EscortPlugin] plugin belongs to this set.
+#[derive(Debug, Clone, Default, SystemSet, PartialEq, Eq, Hash)]
+pub struct EscortSet;
+
+impl Plugin for EscortPlugin {
+    fn build(&self, app: &mut App) {
+        app.rollback_component::<Escort>()
+            .rollback_component::<EscortSprite>()
+            .rollback_component::<EscortHitPoints>();
+
+        app.add_systems(
+            OnEnter(PlayState::StartNewGame),
+            (despawn_escort, spawn_escort)
+                .chain()
+                .in_set(EscortSet)
+                .after(GameLevelsSet),
+        )
+        .add_systems(
+            OnExit(PlayState::StartNextLevel),
+            (despawn_escort, spawn_escort)
+                .chain()
+                .in_set(EscortSet)
+                .after(GameLevelsSet),
+        )
+        .add_systems(
+            OnEnter(PlayState::Running),
+            resume_escort_movement.in_set(EscortSet),
+        )
+        .add_systems(
+            FixedPostUpdate,
+            detect_escort_hits
+                .run_if(in_state(PlayState::Running))
+                .in_set(EscortSet),
+        )
+        .configure_sets(FixedPostUpdate, EscortSet.after(ProjectileSet));
+    }
+}
+
+fn despawn_escort(query: Query<Entity, With<Escort>>, mut commands: Commands) {
+    for escort in query.iter() {
+        trace!(?escort, "Despawning escort");
+        commands.entity(escort).despawn_recursive();
+    }
+}
+
+fn spawn_escort(
+    level_settings: Res<GameLevelSettings>,
+    player_spritesheets: Res<PlayerTextureCollection>,
+    playing_field: Query<Entity, With<PlayingField>>,
+    mut commands: Commands,
+) {
+    let LevelObjective::Escort {
+        texture,
+        hit_points,
+        position,
+        speed,
+        ..
+    } = &level_settings.objective
+    else {
+        return;
+    };
+    let Some(spritesheet) = player_spritesheets.get(texture) else {
+        warn!(texture, "Could not find sprite sheet");
+        return;
+    };
+
+    debug!(?position, hit_points, "Spawning escort");
+    commands
+        .entity(playing_field.single())
+        .with_children(|commands| {
+            commands
+                .spawn((
+                    StateScoped(GameState::Playing),
+                    SpatialBundle {
+                        transform: Transform::from_translation(position.extend(ESCORT_Z_POS)),
+                        ..default()
+                    },
+                    Name::new("Escort"),
+                    Escort,
+                    EscortHitPoints::from(*hit_points),
+                    RigidBody::Kinematic,
+                    Position::new(*position),
+                    LinearVelocity(Vec2::X * *speed),
+                    Wrapping,
+                    PauseMovement,
+                ))
+                .insert_spritesheet(spritesheet, None, || {
+                    (
+                        EscortSprite,
+                        Sensor,
+                        CollisionLayers::new(
+                            [CollisionLayer::Escort],
+                            [CollisionLayer::Asteroids, CollisionLayer::EnemyLaser],
+                        ),
+                    )
+                });
+        });
+}
+
+fn resume_escort_movement(query: Query<Entity, With<Escort>>, mut commands: Commands) {
+    for escort in query.iter() {
+        commands.entity(escort).remove::<PauseMovement>();
+    }
+}
+
+/// Damages the escort when hit by enemy fire, or when an asteroid crashes into it.
+///
+/// The game is over once the escort is destroyed.
+fn detect_escort_hits(
+    mut projectile_hit_events: EventReader<ProjectileCollisionEvent>,
+    mut collision_events: EventReader<CollisionStarted>,
+    escort_collider_query: Query<(), With<EscortSprite>>,
+    asteroid_collider_query: Query<(), With<AsteroidSprite>>,
+    mut escort_query: Query<(Entity, &mut EscortHitPoints)>,
+    mut next: ResMut<NextState<PlayState>>,
+    mut commands: Commands,
+) {
+    let projectile_damage = projectile_hit_events
+        .read()
+        .filter(|event| escort_collider_query.contains(event.entity_hit))
+        .map(|event| event.damage)
+        .sum::<u32>();
+    let asteroid_damage = collision_events
+        .read()
+        .filter(|CollisionStarted(a, b)| {
+            (escort_collider_query.contains(*a) && asteroid_collider_query.contains(*b))
+                || (escort_collider_query.contains(*b) && asteroid_collider_query.contains(*a))
+        })
+        .count() as u32;
+    let damage = projectile_damage + asteroid_damage;
+    if damage == 0 {
+        return;
+    }
+    let Ok((escort, mut hit_points)) = escort_query.get_single_mut() else {
+        return;
+    };
+
+    **hit_points = hit_points.saturating_sub(damage);
+    debug!(?escort, damage, hit_points = **hit_points, "Escort hit");
+    if **hit_points == 0 {
+        warn!("escort destroyed!");
+        commands.entity(escort).despawn_recursive();
+        next.set(PlayState::GameOver(GameOverReason::EscortLost));
+    }
+}
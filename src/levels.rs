@@ -1,14 +1,18 @@
-use bevy::prelude::*;
+use bevy::{prelude::*, time::Stopwatch};
 use smart_default::SmartDefault;
 #[allow(unused)]
 use tracing::*;
 
 use crate::{
     assets::{
-        DefaultLevelSettings, GameLevelSettings, GameLevelSettingsCollection, GameStartSettings,
+        DefaultLevelSettings, EndlessSettings, GameLevelSettings, GameLevelSettingsCollection,
+        GameMode, GameStartSettings, LevelObjective,
     },
-    asteroid::AsteroidCount,
+    asteroid::{AsteroidCount, AsteroidDestroyedEvent, AsteroidSet},
+    enemy::EnemySet,
+    player::{AddToScoreEvent, OutOfLives, Player, PlayerSet},
     projectile::Projectile,
+    rollback::RollbackApp,
     states::{GameOverReason, PlayState},
     GameLevel,
 };
@@ -20,62 +24,115 @@ pub struct GameLevelsPlugin;
 #[derive(Debug, Clone, Default, SystemSet, PartialEq, Eq, Hash)]
 pub struct GameLevelsSet;
 
+/// The wave currently played in [GameMode::Endless], starting at 1.
+#[derive(Resource, Debug, Default, Clone, Copy, Deref, DerefMut)]
+pub struct Wave(usize);
+
+/// Time spent playing the current level, only running in [PlayState::Running].
+#[derive(Resource, Debug, Default, Clone, Deref, DerefMut)]
+pub struct LevelTimer(Stopwatch);
+
+/// Progress towards the [LevelObjective] of the current level.
+#[derive(Resource, Debug, Default, Clone)]
+pub struct ObjectiveProgress {
+    /// points scored by all players during the level
+    pub points: usize,
+    /// asteroids destroyed from the pool of a [LevelObjective::DestroyFromPool]
+    pub destroyed: usize,
+}
+
 impl Plugin for GameLevelsPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(
-            OnEnter(PlayState::StartNewGame),
-            (start_new_game, init_level_settings)
-                .chain()
-                .in_set(GameLevelsSet),
-        )
-        .add_systems(
-            Update,
-            start_after_death
-                .run_if(in_state(PlayState::StartAfterDeath))
-                .in_set(GameLevelsSet),
-        )
-        .add_systems(
-            Update,
-            start_next_level
-                .run_if(in_state(PlayState::StartNextLevel))
-                .in_set(GameLevelsSet),
-        )
-        .add_systems(
-            OnExit(PlayState::StartNextLevel),
-            init_level_settings.in_set(GameLevelsSet),
-        )
-        .add_systems(
-            PostUpdate,
-            detect_level_cleared
-                .run_if(in_state(PlayState::Running))
-                .in_set(GameLevelsSet),
-        );
+        app.init_resource::<Wave>()
+            .init_resource::<LevelTimer>()
+            .init_resource::<ObjectiveProgress>()
+            .rollback_resource::<Wave>()
+            .rollback_resource::<LevelTimer>()
+            .rollback_resource::<ObjectiveProgress>()
+            .add_systems(
+                OnEnter(PlayState::StartNewGame),
+                (start_new_game, init_level_settings, reset_level_progress)
+                    .chain()
+                    .in_set(GameLevelsSet),
+            )
+            .add_systems(
+                FixedUpdate,
+                start_after_death
+                    .run_if(in_state(PlayState::StartAfterDeath))
+                    .in_set(GameLevelsSet),
+            )
+            .add_systems(
+                FixedUpdate,
+                start_next_level
+                    .run_if(in_state(PlayState::StartNextLevel))
+                    .in_set(GameLevelsSet),
+            )
+            .add_systems(
+                OnExit(PlayState::StartNextLevel),
+                (init_level_settings, reset_level_progress)
+                    .chain()
+                    .in_set(GameLevelsSet),
+            )
+            .add_systems(
+                FixedUpdate,
+                (tick_level_timer, detect_time_up.run_if(time_attack_mode))
+                    .chain()
+                    .run_if(in_state(PlayState::Running))
+                    .in_set(GameLevelsSet),
+            )
+            .add_systems(
+                FixedPostUpdate,
+                (track_objective_progress, detect_level_cleared)
+                    .chain()
+                    // counts the hits of the same tick, and the time bonus must be scored
+                    // before leaving PlayState::Running
+                    .after(AsteroidSet)
+                    .after(EnemySet)
+                    .before(PlayerSet)
+                    .run_if(in_state(PlayState::Running))
+                    .run_if(not(versus_mode))
+                    .in_set(GameLevelsSet),
+            );
     }
 }
 
 /// Reads the current level from the [GameLevel] resource and looks up the [GameLevelSettings] to
-/// insert it, and a [PlayerSettings] as resources.
+/// insert it, and a [PlayerSettings] as resources. In [GameMode::Endless], the
+/// [GameLevelSettings] of the current [Wave] are generated instead. Both are adjusted to the
+/// chosen [crate::assets::Difficulty].
 ///
 /// This wastes a little bit of memory, but saves us from looking up and merging settings every
 /// time we need them during the gameplay.
 pub fn init_level_settings(
     current_level: Res<GameLevel>,
+    wave: Res<Wave>,
     level_settings_collection: Res<GameLevelSettingsCollection>,
     default_level_settings: Res<DefaultLevelSettings>,
+    endless_settings: Res<EndlessSettings>,
+    game_start: Res<GameStartSettings>,
     mut commands: Commands,
 ) {
-    let Some(level_settings) = level_settings_collection.get(&**current_level) else {
-        error!(
-            level = &**current_level,
-            "Settings are flawed: Unrecognized level specified"
-        );
-        panic!();
+    let level_settings = match game_start.mode {
+        GameMode::Endless => endless_settings.level_settings(**wave),
+        GameMode::Campaign | GameMode::Versus | GameMode::TimeAttack => {
+            let Some(level_settings) = level_settings_collection.get(&**current_level) else {
+                error!(
+                    level = &**current_level,
+                    "Settings are flawed: Unrecognized level specified"
+                );
+                panic!();
+            };
+            level_settings.clone()
+        }
     };
+    let difficulty = game_start.difficulty_preset();
+    let level_settings = level_settings.apply_difficulty(difficulty);
 
     let player_settings = default_level_settings
         .player
         .clone()
-        .merge(level_settings.player.as_ref());
+        .merge(level_settings.player.as_ref())
+        .apply_difficulty(difficulty);
 
     debug!(
         ?player_settings,
@@ -83,17 +140,42 @@ pub fn init_level_settings(
         "inserting PlayerSettings & LevelSettings as resources"
     );
     commands.insert_resource(player_settings);
-    commands.insert_resource(level_settings.clone());
+    commands.insert_resource(level_settings);
+}
+
+/// Starts the [LevelTimer] and the [ObjectiveProgress] of a level from scratch.
+fn reset_level_progress(
+    mut level_timer: ResMut<LevelTimer>,
+    mut objective_progress: ResMut<ObjectiveProgress>,
+) {
+    level_timer.reset();
+    *objective_progress = ObjectiveProgress::default();
+}
+
+/// Run condition for systems only used in a [GameMode::Versus] match.
+pub fn versus_mode(game_start: Res<GameStartSettings>) -> bool {
+    game_start.mode == GameMode::Versus
+}
+
+/// Run condition for systems only used in [GameMode::TimeAttack].
+pub fn time_attack_mode(game_start: Res<GameStartSettings>) -> bool {
+    game_start.mode == GameMode::TimeAttack
 }
 
 /// Initialize [GameLevel] to the correct starting level.
 fn start_new_game(
     mut next: ResMut<NextState<PlayState>>,
     mut level: ResMut<GameLevel>,
+    mut wave: ResMut<Wave>,
     game_start: Res<GameStartSettings>,
 ) {
-    **level = game_start.level.clone();
-    info!(level = **level, "Starting new game");
+    **wave = 1;
+    **level = match game_start.mode {
+        GameMode::Campaign | GameMode::TimeAttack => game_start.level.clone(),
+        GameMode::Versus => game_start.versus.level.clone(),
+        GameMode::Endless => format!("Wave {}", **wave),
+    };
+    info!(level = **level, mode = ?game_start.mode, "Starting new game");
     next.set(PlayState::CountdownBeforeRunning);
 }
 
@@ -119,15 +201,94 @@ fn start_next_level(
     }
 }
 
-/// Detects when all asteroids have been destroyed, and all projectiles are gone, then transitions
-/// to either [PlayState::GameOver] or [PlayState::StartNextLevel] depending on the current level.
+fn tick_level_timer(time: Res<Time>, mut level_timer: ResMut<LevelTimer>) {
+    level_timer.tick(time.delta());
+}
+
+/// Ends the game when the [GameLevelSettings::time_limit] of the level runs out.
+fn detect_time_up(
+    level_timer: Res<LevelTimer>,
+    level_settings: Res<GameLevelSettings>,
+    mut next: ResMut<NextState<PlayState>>,
+) {
+    if level_settings
+        .time_limit
+        .is_some_and(|limit| level_timer.elapsed() >= limit)
+    {
+        warn!(elapsed = ?level_timer.elapsed(), "time is up!");
+        next.set(PlayState::GameOver(GameOverReason::TimeUp));
+    }
+}
+
+fn track_objective_progress(
+    mut score_events: EventReader<AddToScoreEvent>,
+    mut destroyed_events: EventReader<AsteroidDestroyedEvent>,
+    player_query: Query<(), With<Player>>,
+    level_settings: Res<GameLevelSettings>,
+    mut objective_progress: ResMut<ObjectiveProgress>,
+) {
+    objective_progress.points += score_events
+        .read()
+        .filter(|event| player_query.contains(event.player))
+        .map(|event| event.score)
+        .sum::<usize>();
+    for event in destroyed_events.read() {
+        if let LevelObjective::DestroyFromPool { pool, .. } = &level_settings.objective {
+            if event.pool == *pool {
+                objective_progress.destroyed += 1;
+            }
+        }
+    }
+}
+
+/// Detects when the [LevelObjective] of the level has been met, e.g. all asteroids have been
+/// destroyed, then transitions to either [PlayState::GameOver] or [PlayState::StartNextLevel]
+/// depending on the current level.
+///
+/// Players still in the game are awarded a time bonus for every second left of the
+/// [GameLevelSettings::par_time]. The endless mode never runs out of levels, it just continues
+/// with the next [Wave].
+#[allow(clippy::too_many_arguments)]
 fn detect_level_cleared(
     asteroid_counter: Res<AsteroidCount>,
     level_settings: Res<GameLevelSettings>,
+    level_timer: Res<LevelTimer>,
+    objective_progress: Res<ObjectiveProgress>,
+    game_start: Res<GameStartSettings>,
+    player_query: Query<Entity, (With<Player>, Without<OutOfLives>)>,
     mut current_level: ResMut<GameLevel>,
+    mut wave: ResMut<Wave>,
+    mut score_events: EventWriter<AddToScoreEvent>,
     mut next: ResMut<NextState<PlayState>>,
 ) {
-    if **asteroid_counter == 0 {
+    let objective_met = match &level_settings.objective {
+        LevelObjective::ClearAll => **asteroid_counter == 0,
+        LevelObjective::Survive(duration) | LevelObjective::Escort { duration, .. } => {
+            level_timer.elapsed() >= *duration
+        }
+        LevelObjective::Score(points) => objective_progress.points >= *points,
+        LevelObjective::DestroyFromPool { count, .. } => objective_progress.destroyed >= *count,
+    };
+    if objective_met {
+        if let Some(par_time) = level_settings.par_time {
+            let seconds_left = par_time.saturating_sub(level_timer.elapsed()).as_secs() as usize;
+            let score = seconds_left * game_start.time_bonus_per_second;
+            if score > 0 {
+                info!(seconds_left, score, "cleared the level within par time");
+                for player in player_query.iter() {
+                    score_events.send(AddToScoreEvent { player, score });
+                }
+            }
+        }
+
+        if game_start.mode == GameMode::Endless {
+            **wave += 1;
+            **current_level = format!("Wave {}", **wave);
+            info!(wave = **wave, "wave cleared, starting next wave");
+            next.set(PlayState::StartNextLevel);
+            return;
+        }
+
         let Some(next_level) = &level_settings.next_level else {
             warn!("won the game!");
             next.set(PlayState::GameOver(GameOverReason::GameWon));
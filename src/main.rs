@@ -11,12 +11,15 @@ mod assets;
 mod asteroid;
 mod background;
 mod constants;
+mod director;
+mod enemy;
+mod escort;
 mod levels;
 mod movement;
 mod player;
 mod projectile;
+mod rollback;
 mod states;
-mod tween_events;
 mod ui;
 mod utils;
 
@@ -25,12 +28,15 @@ use crate::{
     asteroid::AsteroidPlugin,
     background::BackgroundPlugin,
     constants::AUDIO_SCALE,
+    director::LevelDirectorPlugin,
+    enemy::EnemyPlugin,
+    escort::EscortPlugin,
     levels::GameLevelsPlugin,
     movement::MovementPlugin,
     player::PlayerPlugin,
     projectile::ProjectilePlugin,
+    rollback::RollbackPlugin,
     states::{GameOverReason, GameState, GameStatesPlugin, PlayState},
-    tween_events::TweenCompletedPlugin,
     ui::UiPlugin,
 };
 
@@ -45,6 +51,12 @@ pub enum CollisionLayer {
     Player,
     Laser,
     Asteroids,
+    Enemy,
+    EnemyLaser,
+    /// Lasers of players, able to hit the other players in versus mode
+    RivalLaser,
+    /// The ship to protect in a level with an escort objective
+    Escort,
 }
 
 fn main() -> AppExit {
@@ -75,14 +87,17 @@ fn main() -> AppExit {
         .add_plugins((RngPlugin::default(), TweeningPlugin));
 
     app.add_plugins((
+        RollbackPlugin,
         GameStatesPlugin,
         GameLevelsPlugin,
         GameAssetsPlugin,
-        TweenCompletedPlugin,
         MovementPlugin,
         PlayerPlugin,
         ProjectilePlugin,
         AsteroidPlugin,
+        EnemyPlugin,
+        EscortPlugin,
+        LevelDirectorPlugin,
         UiPlugin,
         BackgroundPlugin,
     ));
@@ -93,6 +108,8 @@ fn main() -> AppExit {
 
 #[cfg(feature = "cmd_line")]
 mod cmd_line;
+#[cfg(feature = "cmd_line")]
+mod network;
 
 #[allow(unused)]
 fn add_features(app: &mut App) {
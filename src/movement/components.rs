@@ -22,10 +22,10 @@ pub struct ClampMovementSpeed {
     pub range: Range<f32>,
 }
 
-#[derive(Component, Reflect, Debug, Constructor, Deref, DerefMut, From, Into)]
+#[derive(Component, Reflect, Debug, Clone, Constructor, Deref, DerefMut, From, Into)]
 pub struct PausedLinearVelocity(pub LinearVelocity);
 
-#[derive(Component, Reflect, Debug, Constructor, Deref, DerefMut, From, Into)]
+#[derive(Component, Reflect, Debug, Clone, Constructor, Deref, DerefMut, From, Into)]
 pub struct PausedAngularVelocity(pub AngularVelocity);
 
 /// Describes a game area for constraining movement using the [Wrapping] component.
@@ -72,6 +72,24 @@ impl GameArea {
         self.max.y - self.min.y
     }
 
+    /// Whether the position is inside the area, its edges included.
+    pub fn contains(&self, position: Vec2) -> bool {
+        (self.min.x..=self.max.x).contains(&position.x)
+            && (self.min.y..=self.max.y).contains(&position.y)
+    }
+
+    /// The shortest vector from `from` to `to`, which may cross the edges of the area.
+    pub fn shortest_delta(&self, from: Vec2, to: Vec2) -> Vec2 {
+        let mut delta = to - from;
+        if delta.x.abs() > self.width() / 2.0 {
+            delta.x -= self.width().copysign(delta.x);
+        }
+        if delta.y.abs() > self.height() / 2.0 {
+            delta.y -= self.height().copysign(delta.y);
+        }
+        delta
+    }
+
     #[must_use]
     pub fn wrap(&self, position: &Position) -> Position {
         Position::new(Vec2::new(
@@ -1,5 +1,6 @@
 mod components;
 mod plugin;
+mod steering;
 mod systems;
 
-pub use self::{components::*, plugin::*};
+pub use self::{components::*, plugin::*, steering::Steering};
@@ -1,11 +1,13 @@
-use avian2d::PhysicsPlugins;
+use avian2d::prelude::*;
 use bevy::prelude::*;
 
 use crate::{
+    constants::TICKS_PER_SECOND,
     movement::{
-        systems::*, ClampMovementSpeed, GameArea, PausedAngularVelocity, PausedLinearVelocity,
-        WrappingGameAreaOn,
+        steering::init_steering, systems::*, AutoMovementPaused, ClampMovementSpeed, GameArea,
+        PauseMovement, PausedAngularVelocity, PausedLinearVelocity, Wrapping, WrappingGameAreaOn,
     },
+    rollback::RollbackApp,
     states::PlayState,
 };
 
@@ -23,10 +25,25 @@ impl Plugin for MovementPlugin {
             .register_type::<PausedLinearVelocity>()
             .register_type::<PausedAngularVelocity>()
             .register_type::<WrappingGameAreaOn>()
-            .add_plugins(PhysicsPlugins::default());
+            .add_plugins(PhysicsPlugins::default())
+            .insert_resource(Gravity::ZERO)
+            // exactly one physics step per tick, so the game plays out the same every time
+            .insert_resource(Time::<Fixed>::from_hz(TICKS_PER_SECOND))
+            .insert_resource(Time::<Physics>::new_with(Physics::fixed_once_hz(
+                TICKS_PER_SECOND,
+            )));
+
+        app.rollback_component::<GameArea>()
+            .rollback_component::<Wrapping>()
+            .rollback_component::<WrappingGameAreaOn>()
+            .rollback_component::<ClampMovementSpeed>()
+            .rollback_component::<PauseMovement>()
+            .rollback_component::<AutoMovementPaused>()
+            .rollback_component::<PausedLinearVelocity>()
+            .rollback_component::<PausedAngularVelocity>();
 
         app.add_systems(
-            PreUpdate,
+            FixedPreUpdate,
             (
                 decay_linear_movement_velocity.pipe(clamp_linear_movement_velocity),
                 on_wrapping_added,
@@ -44,5 +61,7 @@ impl Plugin for MovementPlugin {
             OnEnter(PlayState::Running),
             auto_resume_movement_when_playing.in_set(MovementSet),
         );
+
+        init_steering(app);
     }
 }
@@ -0,0 +1,224 @@
+use avian2d::prelude::*;
+use bevy::prelude::*;
+use bevy_turborand::{DelegatedRng, RngComponent};
+
+use crate::{
+    assets::{SteeringBehavior, SteeringSettings, SteeringTarget, WeightedSteeringBehavior},
+    asteroid::Asteroid,
+    enemy::Enemy,
+    movement::{ClampMovementSpeed, GameArea, MovementSet, PauseMovement, WrappingGameAreaOn},
+    player::{Dead, Player},
+    rollback::RollbackApp,
+    states::PlayState,
+    CollisionLayer,
+};
+
+pub fn init_steering(app: &mut App) {
+    app.add_systems(
+        FixedUpdate,
+        steer
+            .run_if(in_state(PlayState::Running))
+            .in_set(MovementSet),
+    )
+    .rollback_component::<Steering>();
+}
+
+/// Changes the [LinearVelocity] and [Rotation] of an entity according to its [SteeringSettings].
+#[derive(Component, Debug, Clone)]
+pub struct Steering {
+    settings: SteeringSettings,
+    /// current angle of the wander behavior, relative to the heading
+    wander_angle: f32,
+}
+
+impl Steering {
+    pub fn new(settings: SteeringSettings) -> Self {
+        Self {
+            settings,
+            wander_angle: 0.0,
+        }
+    }
+}
+
+/// Something that steering behaviors can target.
+struct TargetInfo {
+    entity: Entity,
+    kind: SteeringTarget,
+    position: Vec2,
+    velocity: Vec2,
+}
+
+type SteeredQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        Entity,
+        &'static mut Steering,
+        &'static Position,
+        &'static mut LinearVelocity,
+        &'static mut Rotation,
+        Option<&'static ClampMovementSpeed>,
+        Option<&'static WrappingGameAreaOn>,
+        Option<&'static Children>,
+        Option<&'static mut RngComponent>,
+    ),
+    Without<PauseMovement>,
+>;
+
+type TargetQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        Entity,
+        &'static Position,
+        Option<&'static LinearVelocity>,
+        Has<Player>,
+        Has<Asteroid>,
+    ),
+    (
+        Or<(With<Player>, With<Asteroid>, With<Enemy>)>,
+        Without<Dead>,
+    ),
+>;
+
+fn steer(
+    time: Res<Time>,
+    mut queries: ParamSet<(SteeredQuery, TargetQuery)>,
+    game_area_query: Query<&GameArea>,
+    spatial_query_pipeline: Res<SpatialQueryPipeline>,
+) {
+    let delta_seconds = time.delta_seconds();
+    let targets: Vec<_> = queries
+        .p1()
+        .iter()
+        .map(
+            |(entity, position, velocity, is_player, is_asteroid)| TargetInfo {
+                entity,
+                kind: if is_player {
+                    SteeringTarget::ClosestPlayer
+                } else if is_asteroid {
+                    SteeringTarget::ClosestAsteroid
+                } else {
+                    SteeringTarget::ClosestEnemy
+                },
+                position: position.0,
+                velocity: velocity.map_or(Vec2::ZERO, |velocity| velocity.0),
+            },
+        )
+        .collect();
+
+    for (
+        entity,
+        mut steering,
+        position,
+        mut velocity,
+        mut rotation,
+        clamp,
+        area,
+        children,
+        mut rand,
+    ) in queries.p0().iter_mut()
+    {
+        let game_area = area.and_then(|area| game_area_query.get(**area).ok());
+        let delta_to = |target: Vec2| {
+            game_area.map_or(target - position.0, |game_area| {
+                game_area.shortest_delta(position.0, target)
+            })
+        };
+        let closest = |kind: SteeringTarget| {
+            targets
+                .iter()
+                .filter(|target| target.kind == kind && target.entity != entity)
+                .map(|target| (delta_to(target.position), target.velocity))
+                .min_by(|(a, _), (b, _)| a.length_squared().total_cmp(&b.length_squared()))
+        };
+
+        let Steering {
+            settings,
+            wander_angle,
+        } = &mut *steering;
+        let max_speed = settings.max_speed;
+        let heading = velocity.try_normalize().unwrap_or(*rotation * Vec2::X);
+
+        let mut force = Vec2::ZERO;
+        for WeightedSteeringBehavior { behavior, weight } in settings.behaviors.iter() {
+            let behavior_force = match behavior {
+                SteeringBehavior::Seek { target } => closest(*target)
+                    .map_or(Vec2::ZERO, |(delta, _)| {
+                        delta.normalize_or_zero() * max_speed - velocity.0
+                    }),
+
+                SteeringBehavior::Flee { target, radius } => closest(*target)
+                    .filter(|(delta, _)| delta.length() < *radius)
+                    .map_or(Vec2::ZERO, |(delta, _)| {
+                        -delta.normalize_or_zero() * max_speed - velocity.0
+                    }),
+
+                SteeringBehavior::Pursue { target, prediction } => {
+                    closest(*target).map_or(Vec2::ZERO, |(delta, target_velocity)| {
+                        let look_ahead = (delta.length() / max_speed).min(*prediction);
+                        (delta + target_velocity * look_ahead).normalize_or_zero() * max_speed
+                            - velocity.0
+                    })
+                }
+
+                SteeringBehavior::Wander {
+                    distance,
+                    radius,
+                    jitter,
+                } => {
+                    if let Some(rand) = rand.as_deref_mut() {
+                        *wander_angle += rand.f32_normalized() * jitter * delta_seconds;
+                    }
+                    let wander_target = heading * *distance
+                        + Vec2::from_angle(*wander_angle).rotate(heading) * *radius;
+                    wander_target.normalize_or_zero() * max_speed - velocity.0
+                }
+
+                SteeringBehavior::Orbit {
+                    target,
+                    radius,
+                    clockwise,
+                } => closest(*target).map_or(Vec2::ZERO, |(delta, _)| {
+                    let towards_target = delta.normalize_or_zero();
+                    let tangent = if *clockwise {
+                        -towards_target.perp()
+                    } else {
+                        towards_target.perp()
+                    };
+                    // pull towards the orbit, the further away from it the stronger
+                    let correction = (delta.length() - radius) / radius;
+                    (tangent + towards_target * correction).normalize_or_zero() * max_speed
+                        - velocity.0
+                }),
+
+                SteeringBehavior::Avoid { distance } => {
+                    let Ok(direction) = Dir2::new(heading) else {
+                        continue;
+                    };
+                    let filter = SpatialQueryFilter::from_mask(CollisionLayer::Asteroids)
+                        .with_excluded_entities(
+                            children
+                                .map(|children| children.to_vec())
+                                .unwrap_or_default(),
+                        );
+                    spatial_query_pipeline
+                        .cast_ray(position.0, direction, *distance, true, filter)
+                        .map_or(Vec2::ZERO, |hit| {
+                            hit.normal * settings.max_force * (1.0 - hit.time_of_impact / distance)
+                        })
+                }
+            };
+            force += behavior_force * *weight;
+        }
+
+        let force = force.clamp_length_max(settings.max_force);
+        velocity.0 = (velocity.0 + force * delta_seconds).clamp_length_max(max_speed);
+        if let Some(clamp) = clamp {
+            velocity.0 = velocity.0.clamp_length(clamp.range.start, clamp.range.end);
+        }
+        if settings.face_velocity && velocity.0 != Vec2::ZERO {
+            *rotation = Rotation::radians(velocity.y.atan2(velocity.x));
+        }
+    }
+}
@@ -0,0 +1,22 @@
+//! Module for network games of two players over UDP.
+//!
+//! Both instances run the same gameplay from the same seed, one tick of the fixed schedules
+//! after another, and only exchange the inputs of the players, see [NetInput]. Requests of a
+//! player to pause, resume or leave the game are sent along with the inputs, so both instances
+//! change their state in the same tick.
+//!
+//! In [NetworkMode::Rollback], the input of the other player is predicted until it arrives, so
+//! the game runs on without waiting for it. When the prediction turns out wrong, the game goes
+//! back to the [crate::rollback::WorldSnapshot] of that tick and simulates the ticks since again.
+//! [NetworkMode::Lockstep] waits for the inputs of both players before every tick instead, which
+//! is only meant for finding out whether a bug comes from the rollback.
+//!
+//! Only the state registered with [crate::rollback::RollbackApp] goes back. Sounds of the ticks
+//! simulated again may play twice, and entities brought back by a rollback may be iterated in
+//! another order than before. Both instances compare checksums of the gameplay to detect when
+//! the games differ nevertheless.
+
+mod plugin;
+mod session;
+
+pub use self::{plugin::*, session::*};
@@ -0,0 +1,846 @@
+use core::time::Duration;
+use std::{
+    collections::{hash_map::DefaultHasher, BTreeMap, VecDeque},
+    hash::{Hash, Hasher},
+    io,
+    net::{SocketAddr, UdpSocket},
+};
+
+use avian2d::prelude::*;
+use bevy::{
+    app::{FixedMain, RunFixedMainLoop},
+    prelude::*,
+    state::state::StateTransition,
+    time::run_fixed_main_schedule,
+};
+use bevy_turborand::{GlobalRng, RngComponent};
+use leafwing_input_manager::{prelude::*, systems::swap_to_update};
+#[allow(unused)]
+use tracing::*;
+
+use super::session::{
+    GameSetup, NetInput, NetworkMode, NetworkSession, StateRequest, TickInputs, INPUT_DELAY,
+    MAX_PREDICTION,
+};
+use crate::{
+    assets::GameStartSettings,
+    player::{Player, PlayerAction},
+    rollback::WorldSnapshot,
+    states::{GameOverReason, GameState, PlayState},
+    PlayingField,
+};
+
+/// Number of ticks between the checksums compared by both instances.
+const CHECKSUM_INTERVAL: u32 = 30;
+/// Number of ticks the game may be ahead of the other player, before it slows down.
+const TIME_SYNC_TOLERANCE: u32 = 3;
+/// Most state transitions following each other in one tick.
+const MAX_TRANSITIONS: usize = 8;
+
+/// Which side of the network game this instance is on.
+#[derive(Resource, Debug, Clone)]
+pub enum NetworkRole {
+    /// Waits for the other player at the address, deciding the setup of the game
+    Host {
+        bind: SocketAddr,
+        seed: u64,
+        mode: NetworkMode,
+    },
+    /// Joins the game hosted at the address
+    Client { host: SocketAddr },
+}
+
+/// Plugin connecting to the other player instead of showing the main menu, then running the game
+/// together with it.
+#[derive(Debug)]
+pub struct NetworkPlugin {
+    pub role: NetworkRole,
+}
+
+/// All systems added by the [NetworkPlugin] plugin belongs to this set.
+#[derive(Debug, Clone, Default, SystemSet, PartialEq, Eq, Hash)]
+pub struct NetworkSet;
+
+impl Plugin for NetworkPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(self.role.clone())
+            .add_systems(Startup, spawn_network_input)
+            .add_systems(
+                OnEnter(GameState::MainMenu),
+                start_connecting.run_if(run_once()).in_set(NetworkSet),
+            )
+            .add_systems(
+                OnEnter(GameState::Connecting),
+                open_session.in_set(NetworkSet),
+            )
+            .add_systems(
+                Update,
+                wait_for_other_player
+                    .run_if(in_state(GameState::Connecting))
+                    .run_if(resource_exists::<NetworkSession>)
+                    .in_set(NetworkSet),
+            )
+            .add_systems(
+                OnTransition {
+                    exited: GameState::Connecting,
+                    entered: GameState::MainMenu,
+                },
+                |mut commands: Commands| commands.remove_resource::<NetworkSession>(),
+            );
+        add_network_game_systems(app);
+    }
+}
+
+/// The systems running a [NetworkGame] once both players are connected, see
+/// [run_network_ticks].
+fn add_network_game_systems(app: &mut App) {
+    app.add_systems(
+        RunFixedMainLoop,
+        suspend_fixed_main
+            .before(run_fixed_main_schedule)
+            .run_if(resource_exists::<NetworkGame>)
+            .in_set(NetworkSet),
+    )
+    .add_systems(
+        RunFixedMainLoop,
+        (take_over_player_input, run_network_ticks)
+            .chain()
+            .after(run_fixed_main_schedule)
+            // the fixed schedules see the actions of their ticks
+            .before(swap_to_update::<PlayerAction>)
+            .run_if(resource_exists::<NetworkGame>)
+            .in_set(NetworkSet),
+    )
+    .add_systems(
+        Last,
+        capture_state_requests
+            .run_if(resource_exists::<NetworkGame>)
+            .run_if(in_state(GameState::Playing))
+            .in_set(NetworkSet),
+    );
+}
+
+impl NetworkRole {
+    fn open(&self, game_start: &GameStartSettings) -> io::Result<NetworkSession> {
+        match self {
+            NetworkRole::Host { bind, seed, mode } => {
+                info!(?bind, "Waiting for the other player");
+                let setup = GameSetup {
+                    seed: *seed,
+                    mode: *mode,
+                    game_mode: game_start.mode,
+                    difficulty: game_start.difficulty,
+                    level: game_start.level.clone(),
+                };
+                let session = NetworkSession::host(UdpSocket::bind(bind)?, setup)?;
+                debug!(address = ?session.local_addr(), "Listening for the other player");
+                Ok(session)
+            }
+            NetworkRole::Client { host } => {
+                let bind = match host {
+                    SocketAddr::V4(_) => SocketAddr::from(([0, 0, 0, 0], 0)),
+                    SocketAddr::V6(_) => SocketAddr::from(([0; 8], 0)),
+                };
+                info!(?host, "Joining the network game");
+                NetworkSession::join(UdpSocket::bind(bind)?, *host)
+            }
+        }
+    }
+}
+
+/// The progress of a network game, whose ticks are run by [run_network_ticks].
+#[derive(Resource, Debug)]
+pub struct NetworkGame {
+    /// the next tick to simulate
+    tick: u32,
+    /// the next tick to send the input of the local player for
+    next_input: u32,
+    /// time not simulated yet
+    accumulated: Duration,
+    /// the state before every unconfirmed tick simulated so far
+    snapshots: VecDeque<(u32, WorldSnapshot)>,
+    /// changes of the state made by the local player, sent with the next inputs
+    requests: VecDeque<StateRequest>,
+    /// an unconfirmed tick changing the state, which waits until it is confirmed
+    held: Option<u32>,
+    /// checksums after the simulated ticks, sent once the ticks are confirmed
+    checksums: BTreeMap<u32, u64>,
+    /// number of times the game went back to an earlier tick
+    rollbacks: usize,
+}
+
+impl Default for NetworkGame {
+    fn default() -> Self {
+        Self {
+            tick: 0,
+            next_input: INPUT_DELAY,
+            accumulated: Duration::ZERO,
+            snapshots: VecDeque::new(),
+            requests: VecDeque::new(),
+            held: None,
+            checksums: BTreeMap::new(),
+            rollbacks: 0,
+        }
+    }
+}
+
+impl NetworkGame {
+    /// Number of times the game went back to an earlier tick.
+    #[cfg(test)]
+    pub fn rollbacks(&self) -> usize {
+        self.rollbacks
+    }
+
+    /// Puts the gameplay back to the start of the tick, forgetting everything simulated since.
+    fn load_snapshot(&mut self, world: &mut World, tick: u32) -> bool {
+        let Some(index) = self.snapshots.iter().position(|(t, _)| *t == tick) else {
+            error!(tick, "Missing the snapshot of the tick to roll back to");
+            return false;
+        };
+        self.snapshots[index].1.load(world);
+        self.snapshots.truncate(index);
+        self.checksums.split_off(&tick);
+        self.tick = tick;
+        reset_transitions(world);
+        true
+    }
+}
+
+/// Holds the input of the local player, which is only applied once it is sent.
+#[derive(Component, Debug)]
+struct NetworkInput;
+
+/// The fixed schedules, which only run in [run_network_ticks] during a network game.
+#[derive(Resource)]
+struct SuspendedFixedMain(Schedule);
+
+fn spawn_network_input(mut commands: Commands) {
+    commands.spawn((Name::new("Network Input"), NetworkInput));
+}
+
+fn start_connecting(mut next: ResMut<NextState<GameState>>) {
+    next.set(GameState::Connecting);
+}
+
+fn open_session(
+    role: Res<NetworkRole>,
+    game_start: Res<GameStartSettings>,
+    mut next: ResMut<NextState<GameState>>,
+    mut commands: Commands,
+) {
+    match role.open(&game_start) {
+        Ok(session) => commands.insert_resource(session),
+        Err(err) => {
+            error!(?err, role = ?*role, "Could not start the network game");
+            next.set(GameState::MainMenu);
+        }
+    }
+}
+
+/// Starts the game decided by the host once both players are connected.
+fn wait_for_other_player(
+    mut session: ResMut<NetworkSession>,
+    mut game_start: ResMut<GameStartSettings>,
+    mut global_rng: ResMut<GlobalRng>,
+    mut rng_query: Query<&mut RngComponent, With<PlayingField>>,
+    mut next: ResMut<NextState<GameState>>,
+    mut commands: Commands,
+) {
+    if let Err(err) = session.poll() {
+        error!(?err, "Could not connect to the other player");
+        commands.remove_resource::<NetworkSession>();
+        next.set(GameState::MainMenu);
+        return;
+    }
+    let Some(setup) = session.setup() else {
+        return;
+    };
+    info!(
+        ?setup,
+        local_player = session.local_player(),
+        "Network game started"
+    );
+
+    game_start.players = 2;
+    game_start.mode = setup.game_mode;
+    game_start.difficulty = setup.difficulty;
+    game_start.level = setup.level.clone();
+    *global_rng = GlobalRng::with_seed(setup.seed);
+    for mut rng in rng_query.iter_mut() {
+        *rng = RngComponent::from(&mut global_rng);
+    }
+    commands.insert_resource(NetworkGame::default());
+    next.set(GameState::Playing);
+}
+
+/// Moves the keys of the first player to the [NetworkInput], so the actions of all players are
+/// only set by [run_network_ticks].
+fn take_over_player_input(
+    player_query: Query<(Entity, &Player, &InputMap<PlayerAction>)>,
+    network_input_query: Query<Entity, (With<NetworkInput>, Without<InputMap<PlayerAction>>)>,
+    mut commands: Commands,
+) {
+    for (player, Player { index, .. }, input_map) in player_query.iter() {
+        if *index == 0 {
+            if let Ok(network_input) = network_input_query.get_single() {
+                commands
+                    .entity(network_input)
+                    .insert(InputManagerBundle::with_map(input_map.clone()));
+            }
+        }
+        commands.entity(player).remove::<InputMap<PlayerAction>>();
+    }
+}
+
+/// Keeps the fixed schedules from running on their own, see [run_network_ticks].
+fn suspend_fixed_main(world: &mut World) {
+    if let Some(schedule) = world.resource_mut::<Schedules>().remove(FixedMain) {
+        world.insert_resource(SuspendedFixedMain(schedule));
+    }
+}
+
+fn resume_fixed_main(world: &mut World) {
+    if let Some(SuspendedFixedMain(schedule)) = world.remove_resource() {
+        world.resource_mut::<Schedules>().insert(schedule);
+    }
+}
+
+/// Sends the changes of the state made by the local player, e.g. in the pause menu, to both
+/// instances instead of changing the state right away.
+fn capture_state_requests(
+    mut next_game_state: ResMut<NextState<GameState>>,
+    mut next_play_state: ResMut<NextState<PlayState>>,
+    mut game: ResMut<NetworkGame>,
+) {
+    let mut requests = Vec::new();
+    if let NextState::Pending(state) = *next_game_state {
+        match state {
+            GameState::MainMenu => requests.push(StateRequest::MainMenu),
+            _ => warn!(?state, "Ignoring a change of the state in a network game"),
+        }
+        next_game_state.reset();
+    }
+    if let NextState::Pending(state) = *next_play_state {
+        match state {
+            PlayState::Paused => requests.push(StateRequest::Pause),
+            PlayState::CountdownBeforeRunning => requests.push(StateRequest::Resume),
+            PlayState::StartNewGame => requests.push(StateRequest::PlayAgain),
+            _ => warn!(?state, "Ignoring a change of the state in a network game"),
+        }
+        next_play_state.reset();
+    }
+    for request in requests {
+        // e.g. pausing every frame until the window has the focus again
+        if game.requests.back() != Some(&request) {
+            game.requests.push_back(request);
+        }
+    }
+}
+
+/// Runs the ticks of the fixed schedules due by now, with the inputs of both players.
+///
+/// The ticks simulated with a wrong prediction are simulated again first. Ticks changing the
+/// state wait until they are confirmed, as the systems of the transitions, e.g. the UI, can't
+/// be rolled back. Ends the network game when leaving [GameState::Playing], and ends the match
+/// when the other player left.
+fn run_network_ticks(world: &mut World) {
+    resume_fixed_main(world);
+    let result = world.resource_scope(|world, mut session: Mut<NetworkSession>| {
+        world.resource_scope(|world, mut game: Mut<NetworkGame>| {
+            advance_network_game(world, &mut session, &mut game)
+        })
+    });
+    *world.resource_mut::<Time>() = world.resource::<Time<Virtual>>().as_generic();
+
+    match result {
+        Ok(()) if *world.resource::<State<GameState>>() == GameState::Playing => {}
+        Ok(()) => {
+            info!("Network game ended");
+            end_network_game(world);
+        }
+        Err(err) => {
+            warn!(?err, "Lost the connection to the other player");
+            end_network_game(world);
+            let mut player_query = world.query::<&mut ActionState<PlayerAction>>();
+            for mut action_state in player_query.iter_mut(world) {
+                NetInput::default().apply(&mut action_state);
+            }
+            let playing = world
+                .get_resource::<State<PlayState>>()
+                .is_some_and(|state| !matches!(state.get(), PlayState::GameOver(_)));
+            if playing {
+                world
+                    .resource_mut::<NextState<PlayState>>()
+                    .set(PlayState::GameOver(GameOverReason::Disconnected));
+            }
+        }
+    }
+}
+
+fn end_network_game(world: &mut World) {
+    world.remove_resource::<NetworkGame>();
+    world.remove_resource::<NetworkSession>();
+}
+
+fn advance_network_game(
+    world: &mut World,
+    session: &mut NetworkSession,
+    game: &mut NetworkGame,
+) -> io::Result<()> {
+    session.poll()?;
+
+    // the time of the fixed schedules goes by here instead
+    let timestep = world.resource::<Time<Fixed>>().timestep();
+    let overstep = world.resource::<Time<Fixed>>().overstep();
+    world
+        .resource_mut::<Time<Fixed>>()
+        .discard_overstep(overstep);
+    game.accumulated = (game.accumulated + overstep).min(timestep * MAX_PREDICTION);
+    // lets the other player catch up, instead of predicting them all the time
+    if session.ticks_ahead(game.tick) > TIME_SYNC_TOLERANCE {
+        game.accumulated = game.accumulated.saturating_sub(overstep / 10);
+    }
+    // the states entered along with GameState::Playing, before the first tick
+    run_transitions(world);
+
+    if let Some(tick) = session.take_misprediction() {
+        let current = game.tick;
+        if tick < current && game.load_snapshot(world, tick) {
+            game.rollbacks += 1;
+            while game.tick < current && simulate_tick(world, session, game) {}
+        }
+    }
+
+    let lockstep = session
+        .setup()
+        .is_some_and(|setup| setup.mode == NetworkMode::Lockstep);
+    let input = local_input(world);
+    while game.accumulated >= timestep {
+        let tick = game.tick;
+        while game.next_input <= tick + INPUT_DELAY {
+            let input = NetInput {
+                request: game.requests.pop_front(),
+                ..input
+            };
+            session.add_local_input(game.next_input, input)?;
+            game.next_input += 1;
+        }
+        let waiting = match lockstep {
+            true => tick >= session.confirmed(),
+            false => {
+                tick >= session.confirmed() + MAX_PREDICTION
+                    || game.held.is_some() && tick >= session.confirmed()
+            }
+        };
+        if waiting || !simulate_tick(world, session, game) {
+            break;
+        }
+        game.accumulated -= timestep;
+    }
+
+    // nothing before the confirmed tick is simulated again
+    let confirmed = session.confirmed();
+    let unconfirmed = game.checksums.split_off(&confirmed);
+    for (tick, checksum) in core::mem::replace(&mut game.checksums, unconfirmed) {
+        session.add_checksum(tick, checksum)?;
+    }
+    game.snapshots.retain(|(tick, _)| *tick >= confirmed);
+    session.discard_before(game.snapshots.front().map_or(game.tick, |(tick, _)| *tick));
+    Ok(())
+}
+
+/// Runs the next tick, unless it must wait for the inputs of the other player first.
+fn simulate_tick(world: &mut World, session: &mut NetworkSession, game: &mut NetworkGame) -> bool {
+    let tick = game.tick;
+    let inputs = session.inputs(tick);
+    let local_player = session.local_player();
+    if inputs.confirmed {
+        // in the order of the players, the same on both instances
+        let requests = match local_player {
+            0 => [inputs.local.request, inputs.remote.request],
+            _ => [inputs.remote.request, inputs.local.request],
+        };
+        for request in requests.into_iter().flatten() {
+            apply_request(world, request);
+        }
+        run_transitions(world);
+    } else if inputs.local.request.is_some() {
+        game.held = Some(tick);
+        return false;
+    } else {
+        game.snapshots.push_back((tick, WorldSnapshot::save(world)));
+    }
+
+    apply_inputs(world, local_player, &inputs);
+    let timestep = world.resource::<Time<Fixed>>().timestep();
+    *world.resource_mut::<Time>() = tick_time(timestep, tick);
+    world.run_schedule(FixedMain);
+
+    if has_pending_transition(world) {
+        if !inputs.confirmed {
+            game.load_snapshot(world, tick);
+            game.held = Some(tick);
+            return false;
+        }
+        run_transitions(world);
+    }
+    if inputs.confirmed {
+        game.held = None;
+    }
+    if tick.is_multiple_of(CHECKSUM_INTERVAL) {
+        game.checksums.insert(tick, checksum(world));
+    }
+    game.tick += 1;
+    true
+}
+
+/// Changes the state as requested by a player, if it still fits the current state.
+fn apply_request(world: &mut World, request: StateRequest) {
+    let state = world
+        .get_resource::<State<PlayState>>()
+        .map(|state| *state.get());
+    let next = match (request, state) {
+        (StateRequest::Pause, Some(PlayState::Running | PlayState::CountdownBeforeRunning)) => {
+            PlayState::Paused
+        }
+        (StateRequest::Resume, Some(PlayState::Paused)) => PlayState::CountdownBeforeRunning,
+        (StateRequest::PlayAgain, Some(PlayState::GameOver(_))) => PlayState::StartNewGame,
+        (StateRequest::MainMenu, _) => {
+            world
+                .resource_mut::<NextState<GameState>>()
+                .set(GameState::MainMenu);
+            return;
+        }
+        (request, state) => {
+            debug!(?request, ?state, "Ignoring a request not fitting the state");
+            return;
+        }
+    };
+    debug!(?request, ?next, "Applying a request of a player");
+    world.resource_mut::<NextState<PlayState>>().set(next);
+}
+
+/// Changes the state as often as requested, also by the systems entering a state.
+fn run_transitions(world: &mut World) {
+    for _ in 0..MAX_TRANSITIONS {
+        if !has_pending_transition(world) {
+            return;
+        }
+        world.run_schedule(StateTransition);
+    }
+    warn!("Too many state transitions in one tick");
+}
+
+fn has_pending_transition(world: &World) -> bool {
+    matches!(
+        world.get_resource::<NextState<GameState>>(),
+        Some(NextState::Pending(_))
+    ) || matches!(
+        world.get_resource::<NextState<PlayState>>(),
+        Some(NextState::Pending(_))
+    )
+}
+
+fn reset_transitions(world: &mut World) {
+    if let Some(mut next) = world.get_resource_mut::<NextState<GameState>>() {
+        next.reset();
+    }
+    if let Some(mut next) = world.get_resource_mut::<NextState<PlayState>>() {
+        next.reset();
+    }
+}
+
+fn local_input(world: &mut World) -> NetInput {
+    world
+        .query_filtered::<&ActionState<PlayerAction>, (With<NetworkInput>, Without<Player>)>()
+        .get_single(world)
+        .map_or_else(|_| NetInput::default(), NetInput::from_action_state)
+}
+
+fn apply_inputs(world: &mut World, local_player: usize, inputs: &TickInputs) {
+    let mut player_query = world.query::<(&Player, &mut ActionState<PlayerAction>)>();
+    for (player, mut action_state) in player_query.iter_mut(world) {
+        if player.index == local_player {
+            inputs.local.apply(&mut action_state);
+        } else {
+            inputs.remote.apply(&mut action_state);
+        }
+    }
+}
+
+/// The time seen by the systems of the tick, the same on both instances.
+fn tick_time(timestep: Duration, tick: u32) -> Time {
+    let mut time = Time::default();
+    time.advance_by(timestep * tick);
+    time.advance_by(timestep);
+    time
+}
+
+/// Combines the positions and velocities of all bodies, in any order.
+fn checksum(world: &mut World) -> u64 {
+    world
+        .query::<(&Position, &Rotation, Option<&LinearVelocity>)>()
+        .iter(world)
+        .map(|(position, rotation, velocity)| {
+            let velocity = velocity.map_or(Vec2::ZERO, |velocity| velocity.0);
+            let mut hasher = DefaultHasher::new();
+            for value in [
+                position.x,
+                position.y,
+                rotation.as_radians(),
+                velocity.x,
+                velocity.y,
+            ] {
+                value.to_bits().hash(&mut hasher);
+            }
+            hasher.finish()
+        })
+        .fold(0, u64::wrapping_add)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::thread;
+
+    use bevy::{state::app::StatesPlugin, time::TimeUpdateStrategy};
+
+    use super::*;
+    use crate::{
+        assets::{Difficulty, GameMode},
+        rollback::{RollbackApp, RollbackPlugin},
+    };
+
+    const FRAMES: u32 = 240;
+    /// Frames the host runs before the other player, so it must predict their inputs.
+    const HEAD_START: u32 = 4;
+
+    /// A ship flying as the actions of its player say, firing shots which vanish again.
+    #[derive(Component, Debug, Clone)]
+    struct Shot(u32);
+
+    /// Number of the tick, rolled back along with the gameplay.
+    #[derive(Resource, Debug, Clone, Default)]
+    struct SimTick(u32);
+
+    /// The state after every tick, keeping the last simulation of it.
+    #[derive(Resource, Debug, Default)]
+    struct History(BTreeMap<u32, String>);
+
+    /// A change of the state made in the UI of a player.
+    #[derive(Resource, Debug, Default)]
+    struct MenuChoice(Option<PlayState>);
+
+    fn choose_in_menu(mut choice: ResMut<MenuChoice>, mut next: ResMut<NextState<PlayState>>) {
+        if let Some(state) = choice.0.take() {
+            next.set(state);
+        }
+    }
+
+    fn start_running(mut next: ResMut<NextState<PlayState>>) {
+        next.set(PlayState::Running);
+    }
+
+    fn fly(
+        mut player_query: Query<(
+            &ActionState<PlayerAction>,
+            &mut LinearVelocity,
+            &mut Position,
+        )>,
+        mut shot_query: Query<(Entity, &mut Shot)>,
+        playing_field_query: Query<Entity, With<PlayingField>>,
+        mut commands: Commands,
+    ) {
+        for (action_state, mut velocity, mut position) in player_query.iter_mut() {
+            if action_state.pressed(&PlayerAction::Accelerate) {
+                velocity.x += 1.0;
+            }
+            if action_state.pressed(&PlayerAction::TurnLeft) {
+                velocity.y += 1.0;
+            }
+            position.0 += velocity.0;
+            if action_state.pressed(&PlayerAction::Fire) {
+                commands
+                    .entity(playing_field_query.single())
+                    .with_children(|commands| {
+                        commands.spawn((Shot(5), Position(position.0)));
+                    });
+            }
+        }
+        for (shot, mut ttl) in shot_query.iter_mut() {
+            ttl.0 -= 1;
+            if ttl.0 == 0 {
+                commands.entity(shot).despawn_recursive();
+            }
+        }
+    }
+
+    fn record(
+        mut tick: ResMut<SimTick>,
+        mut history: ResMut<History>,
+        state: Res<State<PlayState>>,
+        player_query: Query<(&Player, &Position)>,
+        shot_query: Query<&Shot>,
+    ) {
+        let mut players = player_query
+            .iter()
+            .map(|(player, position)| (player.index, position.0))
+            .collect::<Vec<_>>();
+        players.sort_by_key(|(index, _)| *index);
+        let mut shots = shot_query.iter().map(|shot| shot.0).collect::<Vec<_>>();
+        shots.sort();
+        history
+            .0
+            .insert(tick.0, format!("{:?} {players:?} {shots:?}", state.get()));
+        tick.0 += 1;
+    }
+
+    fn network_app(session: NetworkSession) -> App {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, StatesPlugin, RollbackPlugin))
+            .init_state::<GameState>()
+            .add_sub_state::<PlayState>()
+            .insert_resource(TimeUpdateStrategy::ManualDuration(
+                Time::<Fixed>::default().timestep(),
+            ))
+            .insert_resource(session)
+            .insert_resource(NetworkGame::default())
+            .init_resource::<SimTick>()
+            .init_resource::<History>()
+            .init_resource::<MenuChoice>()
+            .add_systems(Update, choose_in_menu)
+            .rollback_component::<Player>()
+            .rollback_component::<ActionState<PlayerAction>>()
+            .rollback_component::<Shot>()
+            .rollback_resource::<SimTick>()
+            .add_systems(
+                FixedUpdate,
+                (
+                    start_running.run_if(
+                        in_state(PlayState::StartNewGame)
+                            .or_else(in_state(PlayState::CountdownBeforeRunning)),
+                    ),
+                    fly.run_if(in_state(PlayState::Running)),
+                    record,
+                )
+                    .chain()
+                    .run_if(in_state(GameState::Playing)),
+            );
+        add_network_game_systems(&mut app);
+
+        let world = app.world_mut();
+        world.spawn((NetworkInput, ActionState::<PlayerAction>::default()));
+        world
+            .spawn((PlayingField, SpatialBundle::default()))
+            .with_children(|commands| {
+                for index in 0..2 {
+                    commands.spawn((
+                        Player { index, lives: 1 },
+                        ActionState::<PlayerAction>::default(),
+                        Position::default(),
+                        LinearVelocity::default(),
+                    ));
+                }
+            });
+        world
+            .resource_mut::<NextState<GameState>>()
+            .set(GameState::Playing);
+        app
+    }
+
+    /// Changes the input of the local player every few frames, and requests to pause and resume.
+    fn play_frame(app: &mut App, player: usize, frame: u32) {
+        let world = app.world_mut();
+        let mut input_query =
+            world.query_filtered::<&mut ActionState<PlayerAction>, With<NetworkInput>>();
+        let mut action_state = input_query.single_mut(world);
+        let input = NetInput {
+            buttons: ((frame / (3 + player as u32)) % 16) as u8,
+            ..default()
+        };
+        input.apply(&mut action_state);
+        match (player, frame) {
+            (0, 100) => world.resource_mut::<MenuChoice>().0 = Some(PlayState::Paused),
+            (1, 140) => {
+                world.resource_mut::<MenuChoice>().0 = Some(PlayState::CountdownBeforeRunning);
+            }
+            _ => {}
+        }
+        app.update();
+    }
+
+    fn play_over_loopback(mode: NetworkMode) -> (App, App) {
+        let setup = GameSetup {
+            seed: 7,
+            mode,
+            game_mode: GameMode::Versus,
+            difficulty: Difficulty::Normal,
+            level: "Level 1".to_string(),
+        };
+        let mut host =
+            NetworkSession::host(UdpSocket::bind("127.0.0.1:0").unwrap(), setup).unwrap();
+        let mut client = NetworkSession::join(
+            UdpSocket::bind("127.0.0.1:0").unwrap(),
+            host.local_addr().unwrap(),
+        )
+        .unwrap();
+        while host.setup().is_none() || client.setup().is_none() {
+            host.poll().unwrap();
+            client.poll().unwrap();
+            thread::sleep(Duration::from_millis(1));
+        }
+
+        let mut host = network_app(host);
+        let mut client = network_app(client);
+        for frame in 0..FRAMES + HEAD_START {
+            play_frame(&mut host, 0, frame);
+            if frame >= HEAD_START {
+                play_frame(&mut client, 1, frame - HEAD_START);
+            }
+            // lets the packets arrive
+            thread::sleep(Duration::from_millis(1));
+        }
+        (host, client)
+    }
+
+    fn assert_same_game(host: &App, client: &App) {
+        let host_history = &host.world().resource::<History>().0;
+        let client_history = &client.world().resource::<History>().0;
+        // the ticks both simulated with the inputs of both players
+        let confirmed = [host, client]
+            .map(|app| app.world().resource::<NetworkSession>().confirmed())
+            .into_iter()
+            .chain([host_history.len() as u32, client_history.len() as u32])
+            .min()
+            .unwrap();
+        assert!(confirmed > FRAMES / 2, "only confirmed {confirmed} ticks");
+        for tick in 0..confirmed {
+            assert_eq!(
+                host_history.get(&tick),
+                client_history.get(&tick),
+                "tick {tick}"
+            );
+        }
+        let states = host_history.values().collect::<Vec<_>>();
+        assert!(states.iter().any(|state| state.starts_with("Paused")));
+        assert!(states.last().unwrap().starts_with("Running"));
+        for app in [host, client] {
+            assert_eq!(app.world().resource::<NetworkSession>().desynced(), None);
+        }
+    }
+
+    #[test]
+    fn rollback_over_loopback() {
+        let (host, client) = play_over_loopback(NetworkMode::Rollback);
+        assert!(host.world().resource::<NetworkGame>().rollbacks() > 0);
+        assert_same_game(&host, &client);
+    }
+
+    #[test]
+    fn lockstep_over_loopback() {
+        let (host, client) = play_over_loopback(NetworkMode::Lockstep);
+        for app in [&host, &client] {
+            assert_eq!(app.world().resource::<NetworkGame>().rollbacks(), 0);
+        }
+        assert_same_game(&host, &client);
+    }
+}
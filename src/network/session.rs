@@ -0,0 +1,659 @@
+//! The connection to the other instance of a network game, exchanging the inputs of every tick
+//! without ever waiting for them.
+
+use core::time::Duration;
+use std::{
+    collections::BTreeMap,
+    io,
+    net::{SocketAddr, UdpSocket},
+    time::Instant,
+};
+
+use bevy::prelude::*;
+use leafwing_input_manager::prelude::*;
+use strum::IntoEnumIterator;
+#[allow(unused)]
+use tracing::*;
+
+use crate::{
+    assets::{Difficulty, GameMode},
+    player::PlayerAction,
+};
+
+/// Number of ticks between making an input and applying it on both instances.
+pub const INPUT_DELAY: u32 = 2;
+/// Number of ticks the game may run ahead of the inputs of the other player, predicting them.
+pub const MAX_PREDICTION: u32 = 8;
+/// Most inputs sent with one packet, starting at the first one the other instance is missing.
+const MAX_INPUTS_PER_PACKET: usize = 32;
+/// Number of checksums of each instance kept for comparing them.
+const MAX_CHECKSUMS: usize = 16;
+/// Time between packets, sending the same inputs again while the other instance misses them.
+const RESEND_INTERVAL: Duration = Duration::from_millis(20);
+/// Time the host waits for the other player to join.
+const HOST_TIMEOUT: Duration = Duration::from_secs(120);
+/// Time a joining player waits for the host to answer.
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(30);
+/// Time without packets from the other instance, before the connection counts as lost.
+const DISCONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+
+const MAX_PACKET_SIZE: usize = 512;
+/// Sent by the joining player, until the host answers with a [WELCOME].
+const HELLO: u8 = 0;
+/// Answer of the host, followed by the [GameSetup].
+const WELCOME: u8 = 1;
+/// Followed by the first tick still missing from the sender, the tick of the first input and
+/// the inputs of consecutive ticks.
+const INPUTS: u8 = 2;
+/// Followed by a tick and the checksum of the gameplay after it.
+const CHECKSUM: u8 = 3;
+/// Sent when leaving the game.
+const BYE: u8 = 4;
+
+/// Actions sent as buttons, in the order of their bits.
+const BUTTONS: [PlayerAction; 6] = [
+    PlayerAction::Accelerate,
+    PlayerAction::TurnLeft,
+    PlayerAction::TurnRight,
+    PlayerAction::Fire,
+    PlayerAction::HyperJump,
+    PlayerAction::Shield,
+];
+
+/// Requests sent along with an input, in the order of their numbers starting at 1.
+const REQUESTS: [StateRequest; 4] = [
+    StateRequest::Pause,
+    StateRequest::Resume,
+    StateRequest::PlayAgain,
+    StateRequest::MainMenu,
+];
+
+/// How both instances keep the same game running.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum NetworkMode {
+    /// Predicts the inputs of the other player and rolls back when they turn out wrong
+    #[default]
+    Rollback,
+    /// Waits for the inputs of both players before every tick, for debugging the rollback
+    Lockstep,
+}
+
+/// Everything both instances must agree on before the game starts, decided by the host.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GameSetup {
+    pub seed: u64,
+    pub mode: NetworkMode,
+    pub game_mode: GameMode,
+    pub difficulty: Difficulty,
+    pub level: String,
+}
+
+impl GameSetup {
+    const MIN_SIZE: usize = 11;
+
+    fn write(&self, packet: &mut Vec<u8>) {
+        packet.extend_from_slice(&self.seed.to_le_bytes());
+        packet.push(u8::from(self.mode == NetworkMode::Lockstep));
+        packet.push(index_of(self.game_mode));
+        packet.push(index_of(self.difficulty));
+        packet.extend_from_slice(self.level.as_bytes());
+    }
+
+    fn read(bytes: &[u8]) -> Option<Self> {
+        if bytes.len() < Self::MIN_SIZE {
+            return None;
+        }
+        Some(Self {
+            seed: u64::from_le_bytes(bytes[0..8].try_into().unwrap()),
+            mode: match bytes[8] {
+                0 => NetworkMode::Rollback,
+                _ => NetworkMode::Lockstep,
+            },
+            game_mode: GameMode::iter().nth(bytes[9] as usize)?,
+            difficulty: Difficulty::iter().nth(bytes[10] as usize)?,
+            level: String::from_utf8(bytes[Self::MIN_SIZE..].to_vec()).ok()?,
+        })
+    }
+}
+
+fn index_of<T: IntoEnumIterator + PartialEq>(value: T) -> u8 {
+    T::iter()
+        .position(|variant| variant == value)
+        .unwrap_or_default() as u8
+}
+
+/// A change of the state of the game, made by one player for both.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StateRequest {
+    Pause,
+    Resume,
+    PlayAgain,
+    MainMenu,
+}
+
+/// The actions of a player during one tick.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct NetInput {
+    /// pressed [BUTTONS], one bit each
+    pub buttons: u8,
+    /// value of [PlayerAction::AimJump]
+    pub aim: Vec2,
+    pub request: Option<StateRequest>,
+}
+
+impl NetInput {
+    const SIZE: usize = 10;
+
+    pub fn from_action_state(action_state: &ActionState<PlayerAction>) -> Self {
+        let buttons = BUTTONS
+            .iter()
+            .enumerate()
+            .filter(|(_, action)| action_state.pressed(action))
+            .fold(0, |buttons, (bit, _)| buttons | (1 << bit));
+        Self {
+            buttons,
+            aim: action_state.axis_pair(&PlayerAction::AimJump),
+            request: None,
+        }
+    }
+
+    pub fn apply(&self, action_state: &mut ActionState<PlayerAction>) {
+        for (bit, action) in BUTTONS.iter().enumerate() {
+            if self.buttons & (1 << bit) != 0 {
+                action_state.press(action);
+            } else {
+                action_state.release(action);
+            }
+        }
+        action_state.set_axis_pair(&PlayerAction::AimJump, self.aim);
+    }
+
+    /// The input expected in the following ticks, holding the same buttons but not repeating
+    /// the request.
+    fn predicted(&self) -> Self {
+        Self {
+            request: None,
+            ..*self
+        }
+    }
+
+    fn write(&self, packet: &mut Vec<u8>) {
+        packet.push(self.buttons);
+        packet.extend_from_slice(&self.aim.x.to_le_bytes());
+        packet.extend_from_slice(&self.aim.y.to_le_bytes());
+        packet.push(self.request.map_or(0, |request| {
+            REQUESTS.iter().position(|r| *r == request).unwrap() as u8 + 1
+        }));
+    }
+
+    fn read(bytes: &[u8]) -> Self {
+        Self {
+            buttons: bytes[0],
+            aim: Vec2::new(read_f32(&bytes[1..5]), read_f32(&bytes[5..9])),
+            request: bytes[9]
+                .checked_sub(1)
+                .and_then(|index| REQUESTS.get(index as usize))
+                .copied(),
+        }
+    }
+}
+
+/// The inputs of both players to apply in the same tick.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TickInputs {
+    pub local: NetInput,
+    /// predicted, unless the tick is confirmed or its input already arrived
+    pub remote: NetInput,
+    /// the inputs of this and all earlier ticks are known
+    pub confirmed: bool,
+}
+
+/// Connection to the other instance of a network game, exchanging the inputs of every tick.
+///
+/// Every call handles the packets already received and returns right away, see [Self::poll].
+#[derive(Resource, Debug)]
+pub struct NetworkSession {
+    socket: UdpSocket,
+    /// the host for the joining player, the joined player for the host once known
+    peer: Option<SocketAddr>,
+    /// decided by the host, known by the joining player once welcomed
+    setup: Option<GameSetup>,
+    host: bool,
+    started: Instant,
+    last_received: Instant,
+    last_sent: Instant,
+    /// inputs of the local player, kept until the other instance has them and they are final
+    local: BTreeMap<u32, NetInput>,
+    /// inputs of the other player, kept until they are final
+    remote: BTreeMap<u32, NetInput>,
+    /// the first tick without the input of the other player
+    confirmed: u32,
+    /// the first tick without the input of the local player on the other instance
+    acknowledged: u32,
+    /// the newest input of the other player before the [Self::confirmed] tick
+    last_remote: NetInput,
+    /// the newest tick with an input of the other player
+    newest_remote: u32,
+    /// inputs of the other player used for ticks simulated before they arrived
+    predicted: BTreeMap<u32, NetInput>,
+    /// the earliest tick simulated with a wrong prediction
+    mispredicted: Option<u32>,
+    checksums: BTreeMap<u32, u64>,
+    remote_checksums: BTreeMap<u32, u64>,
+    /// the first tick with different checksums on both instances
+    desynced: Option<u32>,
+    /// the other player left the game
+    left: bool,
+}
+
+impl NetworkSession {
+    /// Waits for the other player to join with the socket, telling them the setup of the game.
+    pub fn host(socket: UdpSocket, setup: GameSetup) -> io::Result<Self> {
+        Self::new(socket, None, Some(setup))
+    }
+
+    /// Joins the game hosted at the address with the socket.
+    pub fn join(socket: UdpSocket, host: SocketAddr) -> io::Result<Self> {
+        Self::new(socket, Some(host), None)
+    }
+
+    fn new(
+        socket: UdpSocket,
+        peer: Option<SocketAddr>,
+        setup: Option<GameSetup>,
+    ) -> io::Result<Self> {
+        socket.set_nonblocking(true)?;
+        let now = Instant::now();
+        // nobody acts before the first inputs arrive
+        let no_input = (0..INPUT_DELAY).map(|tick| (tick, NetInput::default()));
+        Ok(Self {
+            socket,
+            peer,
+            host: setup.is_some(),
+            setup,
+            started: now,
+            last_received: now,
+            last_sent: now,
+            local: no_input.clone().collect(),
+            remote: no_input.collect(),
+            confirmed: INPUT_DELAY,
+            acknowledged: INPUT_DELAY,
+            last_remote: NetInput::default(),
+            newest_remote: INPUT_DELAY - 1,
+            predicted: BTreeMap::new(),
+            mispredicted: None,
+            checksums: BTreeMap::new(),
+            remote_checksums: BTreeMap::new(),
+            desynced: None,
+            left: false,
+        })
+    }
+
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.socket.local_addr()
+    }
+
+    /// The setup of the game, once both players are connected.
+    pub fn setup(&self) -> Option<&GameSetup> {
+        self.peer.and(self.setup.as_ref())
+    }
+
+    /// Index of the [crate::player::Player] controlled on this instance, the host plays the
+    /// first one.
+    pub fn local_player(&self) -> usize {
+        match self.host {
+            true => 0,
+            false => 1,
+        }
+    }
+
+    /// The first tick without the input of the other player.
+    pub fn confirmed(&self) -> u32 {
+        self.confirmed
+    }
+
+    /// Number of ticks the tick is ahead of the one the other instance is at.
+    pub fn ticks_ahead(&self, tick: u32) -> u32 {
+        (tick + INPUT_DELAY).saturating_sub(self.newest_remote + 1)
+    }
+
+    /// The first tick with different checksums on both instances, see [Self::add_checksum].
+    #[cfg(test)]
+    pub fn desynced(&self) -> Option<u32> {
+        self.desynced
+    }
+
+    /// Handles the packets received since the last call, and sends the inputs again if the
+    /// other instance may have missed them.
+    ///
+    /// Fails when the other player left or stopped answering.
+    pub fn poll(&mut self) -> io::Result<()> {
+        let mut buffer = [0; MAX_PACKET_SIZE];
+        loop {
+            match self.socket.recv_from(&mut buffer) {
+                Ok((len, from)) => self.handle_packet(&buffer[..len], from)?,
+                Err(err) if err.kind() == io::ErrorKind::WouldBlock => break,
+                Err(err) if is_retry(&err) => {}
+                Err(err) => return Err(err),
+            }
+        }
+        if self.left {
+            return Err(io::Error::new(
+                io::ErrorKind::ConnectionAborted,
+                "the other player left",
+            ));
+        }
+
+        match self.setup() {
+            None if self.host && self.started.elapsed() > HOST_TIMEOUT => Err(io::Error::new(
+                io::ErrorKind::TimedOut,
+                "no other player joined",
+            )),
+            None if self.started.elapsed() > CONNECT_TIMEOUT => Err(io::Error::new(
+                io::ErrorKind::TimedOut,
+                "the host didn't answer",
+            )),
+            None if !self.host && self.last_sent.elapsed() >= RESEND_INTERVAL => {
+                self.send(&[HELLO])
+            }
+            None => Ok(()),
+            Some(_) if self.last_received.elapsed() > DISCONNECT_TIMEOUT => Err(io::Error::new(
+                io::ErrorKind::TimedOut,
+                "the other player stopped answering",
+            )),
+            Some(_) if self.last_sent.elapsed() >= RESEND_INTERVAL => self.send_inputs(),
+            Some(_) => Ok(()),
+        }
+    }
+
+    /// Sends the input of the local player for the tick, which must follow the previous one.
+    pub fn add_local_input(&mut self, tick: u32, input: NetInput) -> io::Result<()> {
+        self.local.insert(tick, input);
+        self.send_inputs()
+    }
+
+    /// The inputs to simulate the tick with, predicting the one of the other player if it
+    /// didn't arrive yet.
+    pub fn inputs(&mut self, tick: u32) -> TickInputs {
+        let local = self.local.get(&tick).copied().unwrap_or_default();
+        let remote = match self.remote.get(&tick) {
+            Some(input) => {
+                self.predicted.remove(&tick);
+                *input
+            }
+            None => {
+                let predicted = self.last_remote.predicted();
+                self.predicted.insert(tick, predicted);
+                predicted
+            }
+        };
+        TickInputs {
+            local,
+            remote,
+            confirmed: tick < self.confirmed,
+        }
+    }
+
+    /// The earliest tick simulated with a wrong prediction since the last call, which must be
+    /// simulated again.
+    pub fn take_misprediction(&mut self) -> Option<u32> {
+        self.mispredicted.take()
+    }
+
+    /// Forgets the inputs before the tick, which won't be simulated again.
+    pub fn discard_before(&mut self, tick: u32) {
+        let tick = tick.min(self.confirmed);
+        // the other instance may still miss some
+        let oldest_local = tick.min(self.acknowledged);
+        self.local
+            .retain(|local_tick, _| *local_tick >= oldest_local);
+        self.remote.retain(|remote_tick, _| *remote_tick >= tick);
+        self.predicted
+            .retain(|predicted_tick, _| *predicted_tick >= tick);
+    }
+
+    /// Sends the checksum of the gameplay after a confirmed tick, to find out whether both
+    /// instances still run the same game.
+    pub fn add_checksum(&mut self, tick: u32, checksum: u64) -> io::Result<()> {
+        let mut packet = vec![CHECKSUM];
+        packet.extend_from_slice(&tick.to_le_bytes());
+        packet.extend_from_slice(&checksum.to_le_bytes());
+        self.checksums.insert(tick, checksum);
+        self.compare_checksums();
+        self.send(&packet)
+    }
+
+    fn send(&mut self, packet: &[u8]) -> io::Result<()> {
+        if let Some(peer) = self.peer {
+            self.socket.send_to(packet, peer)?;
+            self.last_sent = Instant::now();
+        }
+        Ok(())
+    }
+
+    fn send_welcome(&mut self) -> io::Result<()> {
+        let mut packet = vec![WELCOME];
+        if let Some(setup) = &self.setup {
+            setup.write(&mut packet);
+        }
+        self.send(&packet)
+    }
+
+    fn send_inputs(&mut self) -> io::Result<()> {
+        let mut packet = vec![INPUTS];
+        packet.extend_from_slice(&self.confirmed.to_le_bytes());
+        packet.extend_from_slice(&self.acknowledged.to_le_bytes());
+        for (_, input) in self
+            .local
+            .range(self.acknowledged..)
+            .take(MAX_INPUTS_PER_PACKET)
+        {
+            input.write(&mut packet);
+        }
+        self.send(&packet)
+    }
+
+    fn handle_packet(&mut self, packet: &[u8], from: SocketAddr) -> io::Result<()> {
+        if self.host && self.peer.is_none() && packet == [HELLO] {
+            info!(peer = ?from, "The other player joined");
+            self.peer = Some(from);
+        }
+        if Some(from) != self.peer {
+            trace!(?from, "Ignoring packet of a stranger");
+            return Ok(());
+        }
+        self.last_received = Instant::now();
+
+        match packet.first() {
+            // the other player may have missed the welcome
+            Some(&HELLO) if self.host => self.send_welcome()?,
+            Some(&WELCOME) if self.setup.is_none() => {
+                self.setup = GameSetup::read(&packet[1..]);
+                if self.setup.is_none() {
+                    warn!(len = packet.len(), "Ignoring an invalid welcome");
+                }
+            }
+            Some(&INPUTS) if packet.len() >= 9 => self.receive_inputs(
+                read_u32(&packet[1..5]),
+                read_u32(&packet[5..9]),
+                &packet[9..],
+            ),
+            Some(&CHECKSUM) if packet.len() == 13 => {
+                let checksum = u64::from_le_bytes(packet[5..13].try_into().unwrap());
+                self.remote_checksums
+                    .insert(read_u32(&packet[1..5]), checksum);
+                self.compare_checksums();
+            }
+            Some(&BYE) => self.left = true,
+            _ => trace!(len = packet.len(), "Ignoring unexpected packet"),
+        }
+        Ok(())
+    }
+
+    fn receive_inputs(&mut self, acknowledged: u32, first: u32, inputs: &[u8]) {
+        self.acknowledged = self.acknowledged.max(acknowledged);
+        for (tick, bytes) in (first..).zip(inputs.chunks_exact(NetInput::SIZE)) {
+            if tick < self.confirmed || self.remote.contains_key(&tick) {
+                continue;
+            }
+            let input = NetInput::read(bytes);
+            if self
+                .predicted
+                .get(&tick)
+                .is_some_and(|predicted| *predicted != input)
+            {
+                self.mispredicted = Some(self.mispredicted.map_or(tick, |m| m.min(tick)));
+            }
+            self.remote.insert(tick, input);
+            self.newest_remote = self.newest_remote.max(tick);
+        }
+        while let Some(input) = self.remote.get(&self.confirmed) {
+            self.last_remote = *input;
+            self.predicted.remove(&self.confirmed);
+            self.confirmed += 1;
+        }
+    }
+
+    fn compare_checksums(&mut self) {
+        let compared = self
+            .checksums
+            .iter()
+            .filter_map(|(tick, checksum)| {
+                self.remote_checksums
+                    .get(tick)
+                    .map(|remote| (*tick, checksum == remote))
+            })
+            .collect::<Vec<_>>();
+        for (tick, same) in compared {
+            if !same && self.desynced.is_none() {
+                error!(tick, "The network game is out of sync");
+                self.desynced = Some(tick);
+            }
+            self.checksums.remove(&tick);
+            self.remote_checksums.remove(&tick);
+        }
+        // the ones of the other instance may have got lost
+        while self.checksums.len() > MAX_CHECKSUMS {
+            self.checksums.pop_first();
+        }
+        while self.remote_checksums.len() > MAX_CHECKSUMS {
+            self.remote_checksums.pop_first();
+        }
+    }
+}
+
+impl Drop for NetworkSession {
+    fn drop(&mut self) {
+        // tells the other player right away instead of letting them wait for the timeout
+        if self.setup().is_some() {
+            let _ = self.send(&[BYE]);
+        }
+    }
+}
+
+/// Errors of a socket that don't end the connection, e.g. after a packet couldn't be delivered.
+fn is_retry(err: &io::Error) -> bool {
+    matches!(
+        err.kind(),
+        io::ErrorKind::TimedOut | io::ErrorKind::ConnectionReset | io::ErrorKind::ConnectionRefused
+    )
+}
+
+fn read_u32(bytes: &[u8]) -> u32 {
+    u32::from_le_bytes(bytes.try_into().unwrap())
+}
+
+fn read_f32(bytes: &[u8]) -> f32 {
+    f32::from_le_bytes(bytes.try_into().unwrap())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::thread;
+
+    use super::*;
+
+    /// Polls both sessions until the condition holds.
+    fn poll_until(
+        host: &mut NetworkSession,
+        client: &mut NetworkSession,
+        condition: impl Fn(&NetworkSession, &NetworkSession) -> bool,
+    ) {
+        let started = Instant::now();
+        while !condition(host, client) {
+            assert!(started.elapsed() < Duration::from_secs(5), "timed out");
+            host.poll().unwrap();
+            client.poll().unwrap();
+            thread::sleep(Duration::from_millis(1));
+        }
+    }
+
+    #[test]
+    fn inputs_over_loopback() {
+        let setup = GameSetup {
+            seed: 42,
+            mode: NetworkMode::Lockstep,
+            game_mode: GameMode::Versus,
+            difficulty: Difficulty::Hard,
+            level: "Level 2".to_string(),
+        };
+        let mut host =
+            NetworkSession::host(UdpSocket::bind("127.0.0.1:0").unwrap(), setup.clone()).unwrap();
+        let mut client = NetworkSession::join(
+            UdpSocket::bind("127.0.0.1:0").unwrap(),
+            host.local_addr().unwrap(),
+        )
+        .unwrap();
+        poll_until(&mut host, &mut client, |host, client| {
+            host.setup().is_some() && client.setup().is_some()
+        });
+        assert_eq!(client.setup(), Some(&setup));
+        assert_eq!(host.local_player(), 0);
+        assert_eq!(client.local_player(), 1);
+
+        // the client predicts the host stays idle, but the host accelerates and pauses
+        let prediction = client.inputs(INPUT_DELAY);
+        assert!(!prediction.confirmed);
+        assert_eq!(prediction.remote, NetInput::default());
+        let accelerate = NetInput {
+            buttons: 1,
+            aim: Vec2::new(0.5, -1.0),
+            request: Some(StateRequest::Pause),
+        };
+        host.add_local_input(INPUT_DELAY, accelerate).unwrap();
+        client
+            .add_local_input(INPUT_DELAY, NetInput::default())
+            .unwrap();
+        poll_until(&mut host, &mut client, |host, client| {
+            host.confirmed() > INPUT_DELAY && client.confirmed() > INPUT_DELAY
+        });
+
+        assert_eq!(client.take_misprediction(), Some(INPUT_DELAY));
+        assert_eq!(host.take_misprediction(), None);
+        let inputs = client.inputs(INPUT_DELAY);
+        assert!(inputs.confirmed);
+        assert_eq!(inputs.remote, accelerate);
+        assert_eq!(host.inputs(INPUT_DELAY).local, accelerate);
+        // the next prediction holds the buttons but doesn't pause again
+        assert_eq!(
+            client.inputs(INPUT_DELAY + 1).remote,
+            NetInput {
+                request: None,
+                ..accelerate
+            }
+        );
+
+        host.add_checksum(INPUT_DELAY, 1).unwrap();
+        client.add_checksum(INPUT_DELAY, 2).unwrap();
+        poll_until(&mut host, &mut client, |host, client| {
+            host.desynced().is_some() && client.desynced().is_some()
+        });
+
+        drop(host);
+        let started = Instant::now();
+        while client.poll().is_ok() {
+            assert!(started.elapsed() < Duration::from_secs(5), "timed out");
+            thread::sleep(Duration::from_millis(1));
+        }
+    }
+}
@@ -1,9 +1,13 @@
+use core::time::Duration;
+
 use bevy::prelude::*;
 use derive_more::{Constructor, Debug, Deref, DerefMut, Display};
 use serde::{Deserialize, Serialize};
 
 #[derive(Component, Debug, Clone, Copy)]
 pub struct Player {
+    /// position of the player among the local players, starting at 0
+    pub index: usize,
     pub lives: usize,
 }
 
@@ -36,15 +40,72 @@ pub struct Score {
 #[derive(Component, Reflect, Debug, Display, Deref, DerefMut, Constructor, Clone)]
 pub struct EquippedWeapon(String);
 
+/// Number of other players shot down in a versus match.
+#[derive(Component, Debug, Default, Clone, Copy, Deref, DerefMut)]
+pub struct Kills(usize);
+
 #[derive(Component, Debug, Clone, Copy)]
 pub struct Dead;
 
+/// A [Dead] player without any lives left, while other local players are still playing.
 #[derive(Component, Debug, Clone, Copy)]
-pub struct Jumping;
+pub struct OutOfLives;
+
+/// Moves the ship from `start` to `destination`, until the `timer` finishes the jump.
+#[derive(Component, Debug, Clone)]
+pub struct Jumping {
+    pub timer: Timer,
+    pub start: Vec3,
+    pub destination: Vec3,
+}
+
+/// Cooldown and remaining jumps of a player's hyperjump.
+#[derive(Component, Debug, Clone)]
+pub struct JumpDrive {
+    pub cooldown: Timer,
+    /// `None` if the number of jumps is unlimited
+    pub jumps_left: Option<usize>,
+}
+
+impl JumpDrive {
+    /// A drive ready to jump right away.
+    pub fn charged(max_jumps: Option<usize>) -> Self {
+        Self {
+            cooldown: Timer::new(Duration::ZERO, TimerMode::Once),
+            jumps_left: max_jumps,
+        }
+    }
+}
+
+/// Destination of a targeted jump, present while the player is aiming.
+#[derive(Component, Debug, Clone, Copy, Deref, DerefMut)]
+pub struct JumpTarget(pub Vec2);
+
+/// Collisions are ignored while present, e.g. right after a respawn, until the timer finishes.
+#[derive(Component, Debug, Clone, Deref, DerefMut)]
+pub struct Invulnerable(pub Timer);
 
 #[derive(Component, Debug, Clone, Copy)]
 pub struct Accelerating;
 
+/// Present on a player for as long as the fire button is held.
+#[derive(Component, Debug, Clone, Copy)]
+pub struct Firing;
+
+/// Repeats the shots of a player holding the fire button, see [crate::assets::InputKeySettings].
+#[derive(Component, Debug, Clone, Deref, DerefMut)]
+pub struct AutoFire(pub Timer);
+
+/// Energy available to the player's shield.
+#[derive(Component, Debug, Clone, Copy)]
+pub struct Shield {
+    pub energy: f32,
+}
+
+/// Present on a player for as long as the shield is raised.
+#[derive(Component, Debug, Clone, Copy)]
+pub struct ShieldActive;
+
 #[derive(Component, Debug, Clone, PartialEq)]
 pub enum Turning {
     Left(f32),
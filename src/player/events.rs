@@ -13,10 +13,30 @@ pub(crate) struct PlayerDeadEvent {}
 pub(crate) struct PlayerFireEvent;
 
 #[derive(Debug, Clone, Event)]
-pub(crate) struct PlayerJumpingEvent;
+pub(crate) struct PlayerJumpingEvent {
+    /// chosen by the player, or `None` to jump to a random destination
+    pub destination: Option<Vec2>,
+}
+
+/// Triggered on a player starting to aim a targeted jump.
+#[derive(Debug, Clone, Event)]
+pub(crate) struct PlayerAimingEvent;
 
 #[derive(Debug, Clone, Event)]
 pub(crate) struct PlayerJumpFinishedEvent;
 
+/// Triggered on the player when the [crate::player::Invulnerable] time after a respawn is over.
+#[derive(Debug, Clone, Event)]
+pub(crate) struct PlayerInvulnerabilityFinishedEvent;
+
 #[derive(Debug, Clone, Event)]
 pub(crate) struct NewLife;
+
+#[derive(Debug, Clone, Event)]
+pub(crate) struct PlayerShieldEvent;
+
+/// Triggered on a player whose active shield touches asteroids, enemies or enemy projectiles.
+#[derive(Debug, Clone, Event)]
+pub(crate) struct ShieldContactEvent {
+    pub obstacles: Vec<Entity>,
+}
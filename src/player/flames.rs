@@ -3,14 +3,16 @@ use bevy::{audio::Volume, prelude::*};
 use crate::{
     assets::{game_assets::FlameSpriteSheet, EntitySpriteSheetCommands, PlayerSettings},
     player::Accelerating,
+    rollback::RollbackApp,
 };
 
 pub fn init_rocket_flames(app: &mut App) {
     app.observe(on_add_accelerating)
-        .observe(on_remove_accelerating);
+        .observe(on_remove_accelerating)
+        .rollback_component::<RocketFlames>();
 }
 
-#[derive(Component, Debug)]
+#[derive(Component, Debug, Clone)]
 struct RocketFlames;
 
 fn on_add_accelerating(
@@ -2,8 +2,11 @@ use bevy::prelude::*;
 use leafwing_input_manager::prelude::*;
 
 use crate::{
-    assets::InputKeySettings,
-    player::{Accelerating, Jumping, Player, PlayerFireEvent, PlayerJumpingEvent, Turning},
+    assets::{InputKeySettings, JumpMode, PlayerSettings},
+    player::{
+        Accelerating, AutoFire, Dead, Firing, JumpTarget, Jumping, Player, PlayerAimingEvent,
+        PlayerFireEvent, PlayerJumpingEvent, PlayerShieldEvent, ShieldActive, Turning,
+    },
 };
 
 /// Actions that can be performed by the player.
@@ -14,73 +17,135 @@ pub enum PlayerAction {
     TurnRight,
     Fire,
     HyperJump,
+    Shield,
+    /// Moves the reticle of a targeted jump
+    #[actionlike(DualAxis)]
+    AimJump,
 }
 
 pub fn accept_player_input(
-    input_query: Query<
+    mut input_query: Query<
         (
             Entity,
             &ActionState<PlayerAction>,
             Option<&Jumping>,
             Option<&Accelerating>,
             Option<&Turning>,
+            Option<&Firing>,
+            Option<&ShieldActive>,
+            Option<&JumpTarget>,
+            Option<&mut AutoFire>,
         ),
-        With<Player>,
+        (With<Player>, Without<Dead>),
     >,
     mut commands: Commands,
     time: Res<Time>,
     input_settings: Res<InputKeySettings>,
-    mut fire_timer: Local<Option<Timer>>,
+    player_settings: Res<PlayerSettings>,
 ) {
-    let Ok((player, action_state, jumping, acceleration, turning)) = input_query.get_single()
-    else {
-        return;
-    };
-
-    if action_state.pressed(&PlayerAction::HyperJump) && jumping.is_none() {
-        commands.trigger_targets(PlayerJumpingEvent, player);
-    }
-
-    if action_state.pressed(&PlayerAction::Fire) && jumping.is_none() {
-        if let Some(fire_timer) = fire_timer.as_mut() {
-            fire_timer.tick(time.delta());
-            if fire_timer.just_finished() {
-                commands.trigger_targets(PlayerFireEvent, player);
+    for (
+        player,
+        action_state,
+        jumping,
+        acceleration,
+        turning,
+        firing,
+        shield_active,
+        jump_target,
+        auto_fire,
+    ) in input_query.iter_mut()
+    {
+        let jump_pressed = action_state.pressed(&PlayerAction::HyperJump) && jumping.is_none();
+        match (player_settings.jump_mode, jump_target, jump_pressed) {
+            (JumpMode::Random, _, true) => {
+                commands.trigger_targets(PlayerJumpingEvent { destination: None }, player);
             }
-        } else {
-            *fire_timer = Some(Timer::new(input_settings.auto_fire, TimerMode::Repeating));
-            commands.trigger_targets(PlayerFireEvent, player);
+            (JumpMode::Targeted { .. }, None, true) => {
+                commands.trigger_targets(PlayerAimingEvent, player);
+            }
+            (_, Some(target), false) => {
+                commands.entity(player).remove::<JumpTarget>();
+                if jumping.is_none() {
+                    commands.trigger_targets(
+                        PlayerJumpingEvent {
+                            destination: Some(**target),
+                        },
+                        player,
+                    );
+                }
+            }
+            (_, _, _) => {}
         }
-    } else {
-        *fire_timer = None;
-    }
 
-    let acceleration_pressed = action_state.pressed(&PlayerAction::Accelerate);
-    match (acceleration, acceleration_pressed, jumping) {
-        (None, true, None) => {
-            commands.entity(player).insert(Accelerating);
-        }
-        (Some(_), false, _) => {
-            commands.entity(player).remove::<Accelerating>();
+        let shield_pressed = action_state.pressed(&PlayerAction::Shield);
+        match (shield_active, shield_pressed) {
+            (None, true) if action_state.just_pressed(&PlayerAction::Shield) => {
+                commands.trigger_targets(PlayerShieldEvent, player);
+            }
+            (Some(_), false) => {
+                commands.entity(player).remove::<ShieldActive>();
+            }
+            (_, _) => {}
         }
-        (_, _, _) => {}
-    }
 
-    let turn_left = action_state.pressed(&PlayerAction::TurnLeft);
-    let turn_right = action_state.pressed(&PlayerAction::TurnRight);
-    match (turning, turn_left, turn_right) {
-        (None, true, false) | (Some(Turning::Right(..)), true, false) => {
-            trace!(?turning, ?turn_left, ?turn_right, "turning left");
-            commands.entity(player).insert(Turning::Left(0.0));
+        let fire_pressed = action_state.pressed(&PlayerAction::Fire) && jumping.is_none();
+        match (firing, fire_pressed) {
+            (None, true) => {
+                commands.entity(player).insert(Firing);
+            }
+            (Some(_), false) => {
+                commands.entity(player).remove::<Firing>();
+            }
+            (_, _) => {}
         }
-        (None, false, true) | (Some(Turning::Left(..)), false, true) => {
-            trace!(?turning, ?turn_left, ?turn_right, "turning right");
-            commands.entity(player).insert(Turning::Right(0.0));
+
+        match (auto_fire, fire_pressed) {
+            (Some(mut auto_fire), true) => {
+                auto_fire.tick(time.delta());
+                if auto_fire.just_finished() {
+                    commands.trigger_targets(PlayerFireEvent, player);
+                }
+            }
+            (None, true) => {
+                commands.entity(player).insert(AutoFire(Timer::new(
+                    input_settings.auto_fire,
+                    TimerMode::Repeating,
+                )));
+                commands.trigger_targets(PlayerFireEvent, player);
+            }
+            (Some(_), false) => {
+                commands.entity(player).remove::<AutoFire>();
+            }
+            (None, false) => {}
         }
-        (Some(_), false, false) | (Some(_), true, true) => {
-            trace!(?turning, ?turn_left, ?turn_right, "stopped turning");
-            commands.entity(player).remove::<Turning>();
+
+        let acceleration_pressed = action_state.pressed(&PlayerAction::Accelerate);
+        match (acceleration, acceleration_pressed, jumping) {
+            (None, true, None) => {
+                commands.entity(player).insert(Accelerating);
+            }
+            (Some(_), false, _) => {
+                commands.entity(player).remove::<Accelerating>();
+            }
+            (_, _, _) => {}
         }
-        (_, _, _) => {}
-    };
+
+        let turn_left = action_state.pressed(&PlayerAction::TurnLeft);
+        let turn_right = action_state.pressed(&PlayerAction::TurnRight);
+        match (turning, turn_left, turn_right) {
+            (None, true, false) | (Some(Turning::Right(..)), true, false) => {
+                trace!(?turning, ?turn_left, ?turn_right, "turning left");
+                commands.entity(player).insert(Turning::Left(0.0));
+            }
+            (None, false, true) | (Some(Turning::Left(..)), false, true) => {
+                trace!(?turning, ?turn_left, ?turn_right, "turning right");
+                commands.entity(player).insert(Turning::Right(0.0));
+            }
+            (Some(_), false, false) | (Some(_), true, true) => {
+                trace!(?turning, ?turn_left, ?turn_right, "stopped turning");
+                commands.entity(player).remove::<Turning>();
+            }
+            (_, _, _) => {}
+        };
+    }
 }
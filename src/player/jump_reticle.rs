@@ -0,0 +1,133 @@
+use avian2d::prelude::*;
+use bevy::{
+    color::palettes::css,
+    prelude::*,
+    sprite::{MaterialMesh2dBundle, Mesh2dHandle},
+};
+use leafwing_input_manager::prelude::*;
+
+use crate::{
+    assets::{GameAreaSettings, JumpMode, PlayerSettings},
+    constants::JUMP_RETICLE_Z_POS,
+    player::{input::PlayerAction, JumpTarget, Player, PlayerAimingEvent, PlayerSet},
+    rollback::RollbackApp,
+    states::PlayState,
+    PlayingField,
+};
+
+pub fn init_jump_reticle(app: &mut App) {
+    app.add_systems(
+        FixedUpdate,
+        move_jump_reticle
+            .run_if(in_state(PlayState::Running))
+            .in_set(PlayerSet),
+    )
+    .add_systems(
+        OnExit(PlayState::Running),
+        cancel_jump_target.in_set(PlayerSet),
+    )
+    .observe(on_player_aiming)
+    .observe(on_remove_jump_target)
+    .rollback_component::<JumpReticle>();
+}
+
+#[derive(Component, Debug, Clone)]
+struct JumpReticle {
+    player: Entity,
+}
+
+/// Places the reticle in front of the ship, just beyond the minimum jump distance.
+#[allow(clippy::too_many_arguments)]
+fn on_player_aiming(
+    trigger: Trigger<PlayerAimingEvent>,
+    player_query: Query<(&Position, &Rotation), With<Player>>,
+    playing_field_query: Query<Entity, With<PlayingField>>,
+    game_area_settings: Res<GameAreaSettings>,
+    player_settings: Res<PlayerSettings>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    mut commands: Commands,
+) {
+    let player = trigger.entity();
+    let Ok((position, rotation)) = player_query.get(player) else {
+        return;
+    };
+    let game_area = &game_area_settings.game_area;
+    let target = (position.0 + *rotation * Vec2::X * (player_settings.minimum_jump_distance + 1.0))
+        .clamp(game_area.min().truncate(), game_area.max().truncate());
+
+    commands.entity(player).insert(JumpTarget(target));
+    commands
+        .entity(playing_field_query.single())
+        .with_children(|children| {
+            children.spawn((
+                Name::new("Jump Reticle"),
+                JumpReticle { player },
+                MaterialMesh2dBundle {
+                    mesh: Mesh2dHandle(meshes.add(Annulus::new(14.0, 18.0))),
+                    material: materials.add(Color::from(css::VIOLET)),
+                    transform: Transform::from_translation(target.extend(JUMP_RETICLE_Z_POS)),
+                    ..default()
+                },
+            ));
+        });
+    trace!(?player, ?target, "Aiming jump");
+}
+
+/// Moves the reticle with the aim keys, showing whether the target is a valid destination.
+fn move_jump_reticle(
+    mut player_query: Query<(
+        Entity,
+        &ActionState<PlayerAction>,
+        &Position,
+        &mut JumpTarget,
+    )>,
+    mut reticle_query: Query<(&JumpReticle, &mut Transform, &Handle<ColorMaterial>)>,
+    game_area_settings: Res<GameAreaSettings>,
+    player_settings: Res<PlayerSettings>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    time: Res<Time>,
+) {
+    let JumpMode::Targeted { reticle_speed } = player_settings.jump_mode else {
+        return;
+    };
+    let game_area = &game_area_settings.game_area;
+
+    for (player, action_state, position, mut target) in player_query.iter_mut() {
+        let direction = action_state
+            .axis_pair(&PlayerAction::AimJump)
+            .clamp_length_max(1.0);
+        **target = (**target + direction * reticle_speed * time.delta_seconds())
+            .clamp(game_area.min().truncate(), game_area.max().truncate());
+        // same check as for the jump itself, see `on_player_jumping`
+        let valid = player_settings.is_valid_jump_target(game_area, position.0, **target);
+
+        for (reticle, mut transform, material) in reticle_query.iter_mut() {
+            if reticle.player != player {
+                continue;
+            }
+            transform.translation = target.extend(JUMP_RETICLE_Z_POS);
+            if let Some(material) = materials.get_mut(material) {
+                material.color = if valid { css::VIOLET } else { css::RED }.into();
+            }
+        }
+    }
+}
+
+fn cancel_jump_target(player_query: Query<Entity, With<JumpTarget>>, mut commands: Commands) {
+    for player in player_query.iter() {
+        commands.entity(player).remove::<JumpTarget>();
+    }
+}
+
+fn on_remove_jump_target(
+    trigger: Trigger<OnRemove, JumpTarget>,
+    reticle_query: Query<(Entity, &JumpReticle)>,
+    mut commands: Commands,
+) {
+    for (reticle_entity, reticle) in reticle_query.iter() {
+        if reticle.player == trigger.entity() {
+            commands.entity(reticle_entity).despawn_recursive();
+        }
+    }
+}
@@ -2,12 +2,15 @@ mod components;
 mod events;
 mod flames;
 mod input;
+mod jump_reticle;
 mod plugin;
+mod shield;
 mod systems;
+mod versus;
 
 #[cfg(feature = "dbg_colliders")]
 mod dbg_colliders;
 
-pub use self::{components::*, events::*, plugin::*};
+pub use self::{components::*, events::*, input::PlayerAction, plugin::*};
 
 use self::systems::*;
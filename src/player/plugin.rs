@@ -1,16 +1,29 @@
+use avian2d::prelude::*;
 use bevy::prelude::*;
 use leafwing_input_manager::prelude::*;
 
 use crate::{
     levels::GameLevelsSet,
     player::{
-        clear_safe_radius, despawn_old_player, detect_player_collisions,
+        apply_flight_model, apply_newtonian_thrust, clear_safe_radius, despawn_old_player,
+        detect_player_collisions,
         flames::init_rocket_flames,
         input::{accept_player_input, PlayerAction},
-        on_new_life, on_player_death, on_player_firing, on_player_jump_finished, on_player_jumping,
-        player_acceleration_and_turning, reset_player_movement_system, resume_player_movement,
-        spawn_new_player, stop_accelerating, update_player_score, AddToScoreEvent, Player, Score,
+        jump_reticle::init_jump_reticle,
+        move_jumping_players, on_new_life, on_player_death, on_player_firing,
+        on_player_invulnerability_finished, on_player_jump_finished, on_player_jumping,
+        pause_player_animations, player_acceleration_and_turning, recharge_jump_drive,
+        reset_player_movement_system, resume_player_animations, resume_player_movement,
+        shield::init_shield,
+        spawn_new_player, start_invulnerability, stop_accelerating, stop_firing,
+        update_invulnerability, update_jump_cooldown, update_player_score,
+        versus::init_versus,
+        Accelerating, AddToScoreEvent, AutoFire, Dead, EquippedWeapon, Firing, Invulnerable,
+        JumpDrive, JumpTarget, Jumping, Kills, OutOfLives, Player, PlayerSprite, Score, Shield,
+        ShieldActive, Turning,
     },
+    projectile::ProjectileSet,
+    rollback::RollbackApp,
     PlayState,
 };
 
@@ -28,12 +41,32 @@ impl Plugin for PlayerPlugin {
             .add_event::<AddToScoreEvent>()
             .add_plugins(InputManagerPlugin::<PlayerAction>::default());
 
+        app.rollback_component::<Player>()
+            .rollback_component::<PlayerSprite>()
+            .rollback_component::<ActionState<PlayerAction>>()
+            .rollback_component::<Score>()
+            .rollback_component::<Kills>()
+            .rollback_component::<EquippedWeapon>()
+            .rollback_component::<Dead>()
+            .rollback_component::<OutOfLives>()
+            .rollback_component::<Accelerating>()
+            .rollback_component::<Turning>()
+            .rollback_component::<Firing>()
+            .rollback_component::<AutoFire>()
+            .rollback_component::<Jumping>()
+            .rollback_component::<JumpDrive>()
+            .rollback_component::<JumpTarget>()
+            .rollback_component::<Invulnerable>()
+            .rollback_component::<Shield>()
+            .rollback_component::<ShieldActive>();
+
         // transitions
         app.add_systems(
             OnEnter(PlayState::StartNewGame),
             (
                 despawn_old_player.run_if(any_with_component::<Player>),
                 spawn_new_player,
+                apply_flight_model,
             )
                 .chain()
                 .in_set(PlayerSet)
@@ -41,47 +74,68 @@ impl Plugin for PlayerPlugin {
         )
         .add_systems(
             OnExit(PlayState::StartNextLevel),
-            reset_player_movement_system
+            (reset_player_movement_system, apply_flight_model)
+                .chain()
                 .in_set(PlayerSet)
                 .after(GameLevelsSet),
         )
         .add_systems(
             OnExit(PlayState::StartAfterDeath),
-            clear_safe_radius.in_set(PlayerSet).after(GameLevelsSet),
+            (
+                clear_safe_radius,
+                start_invulnerability,
+                recharge_jump_drive,
+            )
+                .in_set(PlayerSet)
+                .after(GameLevelsSet),
         )
         .add_systems(
             OnEnter(PlayState::Running),
-            resume_player_movement.in_set(PlayerSet),
+            (resume_player_movement, resume_player_animations).in_set(PlayerSet),
         )
         .add_systems(
             OnExit(PlayState::Running),
-            stop_accelerating.in_set(PlayerSet),
+            (stop_accelerating, stop_firing, pause_player_animations).in_set(PlayerSet),
         );
 
         app.add_systems(
-            PreUpdate,
+            FixedPreUpdate,
             (accept_player_input)
                 .run_if(in_state(PlayState::Running))
                 .in_set(PlayerSet),
         );
         app.add_systems(
-            Update,
-            (player_acceleration_and_turning,)
+            FixedUpdate,
+            (
+                player_acceleration_and_turning,
+                apply_newtonian_thrust,
+                update_jump_cooldown,
+                move_jumping_players,
+                update_invulnerability,
+            )
                 .run_if(in_state(PlayState::Running))
                 .in_set(PlayerSet),
         )
         .add_systems(
-            PostUpdate,
+            FixedPostUpdate,
             (detect_player_collisions, update_player_score)
                 .run_if(in_state(PlayState::Running))
                 .in_set(PlayerSet),
         )
+        .configure_sets(
+            FixedPostUpdate,
+            PlayerSet.after(ProjectileSet).after(PhysicsSet::Sync),
+        )
         .observe(on_player_death)
         .observe(on_player_firing)
         .observe(on_player_jumping)
         .observe(on_player_jump_finished)
+        .observe(on_player_invulnerability_finished)
         .observe(on_new_life);
 
         init_rocket_flames(app);
+        init_shield(app);
+        init_jump_reticle(app);
+        init_versus(app);
     }
 }
@@ -0,0 +1,232 @@
+use avian2d::prelude::*;
+use bevy::{
+    prelude::*,
+    sprite::{MaterialMesh2dBundle, Mesh2dHandle},
+};
+
+use crate::{
+    assets::{PlayerSettings, ShieldContact},
+    asteroid::{Asteroid, AsteroidHitEvent},
+    constants::SHIELD_RELATIVE_Z_POS,
+    enemy::{Enemy, EnemyHitEvent},
+    player::{Player, PlayerSet, PlayerShieldEvent, Shield, ShieldActive, ShieldContactEvent},
+    rollback::RollbackApp,
+    states::PlayState,
+    CollisionLayer,
+};
+
+pub fn init_shield(app: &mut App) {
+    app.add_systems(
+        FixedUpdate,
+        update_shield_energy
+            .run_if(in_state(PlayState::Running))
+            .in_set(PlayerSet),
+    )
+    .add_systems(
+        OnExit(PlayState::StartAfterDeath),
+        recharge_shield.in_set(PlayerSet),
+    )
+    .add_systems(
+        OnExit(PlayState::StartNextLevel),
+        recharge_shield.in_set(PlayerSet),
+    )
+    .observe(on_player_shield)
+    .observe(on_add_shield_active)
+    .observe(on_remove_shield_active)
+    .observe(on_shield_contact)
+    .rollback_component::<ShieldSprite>();
+}
+
+/// The bubble around the ship, its sensor of [PlayerSettings::shield_radius] picks up everything
+/// the shield deflects.
+#[derive(Component, Debug, Clone)]
+pub(super) struct ShieldSprite;
+
+fn on_player_shield(
+    trigger: Trigger<PlayerShieldEvent>,
+    mut shield_query: Query<&mut Shield, Without<ShieldActive>>,
+    player_settings: Res<PlayerSettings>,
+    mut commands: Commands,
+) {
+    let player = trigger.entity();
+    let Ok(mut shield) = shield_query.get_mut(player) else {
+        return;
+    };
+    if shield.energy < player_settings.shield_activation_cost {
+        trace!(
+            ?player,
+            energy = shield.energy,
+            "Not enough energy to raise shield"
+        );
+        return;
+    }
+    shield.energy -= player_settings.shield_activation_cost;
+    commands.entity(player).insert(ShieldActive);
+}
+
+/// Drains the energy of active shields, lowering them when empty, and regenerates inactive ones.
+fn update_shield_energy(
+    mut shield_query: Query<(Entity, &mut Shield, Has<ShieldActive>), With<Player>>,
+    player_settings: Res<PlayerSettings>,
+    time: Res<Time>,
+    mut commands: Commands,
+) {
+    for (player, mut shield, active) in shield_query.iter_mut() {
+        if active {
+            shield.energy -= player_settings.shield_drain * time.delta_seconds();
+            if shield.energy <= 0.0 {
+                shield.energy = 0.0;
+                debug!(?player, "Shield depleted");
+                commands.entity(player).remove::<ShieldActive>();
+            }
+        } else {
+            shield.energy = (shield.energy
+                + player_settings.shield_regeneration * time.delta_seconds())
+            .min(player_settings.shield_capacity);
+        }
+    }
+}
+
+fn recharge_shield(
+    mut shield_query: Query<(Entity, &mut Shield), With<Player>>,
+    player_settings: Res<PlayerSettings>,
+    mut commands: Commands,
+) {
+    for (player, mut shield) in shield_query.iter_mut() {
+        shield.energy = player_settings.shield_capacity;
+        commands.entity(player).remove::<ShieldActive>();
+    }
+}
+
+fn on_add_shield_active(
+    trigger: Trigger<OnAdd, ShieldActive>,
+    player_settings: Res<PlayerSettings>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    mut commands: Commands,
+) {
+    commands.entity(trigger.entity()).with_children(|children| {
+        children.spawn((
+            Name::new("Shield"),
+            ShieldSprite,
+            MaterialMesh2dBundle {
+                mesh: Mesh2dHandle(meshes.add(Circle::new(player_settings.shield_radius))),
+                material: materials.add(Color::srgba(0.3, 0.6, 1.0, 0.35)),
+                transform: Transform::from_xyz(0.0, 0.0, SHIELD_RELATIVE_Z_POS),
+                ..default()
+            },
+            Collider::circle(player_settings.shield_radius),
+            Sensor,
+            // the bubble must not add to the mass of the ship
+            ColliderDensity(0.0),
+            CollisionLayers::new(
+                [CollisionLayer::Player],
+                [
+                    CollisionLayer::Asteroids,
+                    CollisionLayer::Enemy,
+                    CollisionLayer::EnemyLaser,
+                    CollisionLayer::RivalLaser,
+                ],
+            ),
+        ));
+    });
+    trace!("Raised shield");
+}
+
+fn on_remove_shield_active(
+    trigger: Trigger<OnRemove, ShieldActive>,
+    mut commands: Commands,
+    shield_sprite_query: Query<(Entity, &Parent), With<ShieldSprite>>,
+) {
+    for (shield_sprite, player) in shield_sprite_query.iter() {
+        if player.get() == trigger.entity() {
+            trace!("Lowered shield");
+            commands.entity(shield_sprite).despawn_recursive();
+        }
+    }
+}
+
+/// Deflects everything touching an active shield. Asteroids and enemies are either bounced off or
+/// hit, depending on [PlayerSettings::shield_contact], enemy projectiles are absorbed.
+fn on_shield_contact(
+    trigger: Trigger<ShieldContactEvent>,
+    mut player_query: Query<(&mut Shield, &Position, Option<&LinearVelocity>), With<Player>>,
+    mut obstacle_query: Query<
+        (
+            &Transform,
+            &Position,
+            Option<&mut LinearVelocity>,
+            Has<Asteroid>,
+            Has<Enemy>,
+        ),
+        Without<Player>,
+    >,
+    player_settings: Res<PlayerSettings>,
+    mut commands: Commands,
+) {
+    let player = trigger.entity();
+    let Ok((mut shield, player_position, player_velocity)) = player_query.get_mut(player) else {
+        return;
+    };
+    let player_velocity = player_velocity.map_or(Vec2::ZERO, |velocity| velocity.0);
+
+    for obstacle in trigger.event().obstacles.iter() {
+        if shield.energy == 0.0 {
+            break;
+        }
+        let Ok((transform, position, velocity, is_asteroid, is_enemy)) =
+            obstacle_query.get_mut(*obstacle)
+        else {
+            continue;
+        };
+
+        if !is_asteroid && !is_enemy {
+            trace!(?obstacle, "Shield absorbed projectile");
+            commands.entity(*obstacle).despawn_recursive();
+            shield.energy = (shield.energy - player_settings.shield_hit_cost).max(0.0);
+            continue;
+        }
+
+        // obstacles already moving away were deflected before and are still overlapping
+        let away = (position.0 - player_position.0).normalize_or_zero();
+        let relative_velocity =
+            velocity.as_ref().map_or(Vec2::ZERO, |velocity| velocity.0) - player_velocity;
+        if relative_velocity.dot(away) >= 0.0 {
+            continue;
+        }
+        shield.energy = (shield.energy - player_settings.shield_hit_cost).max(0.0);
+
+        match player_settings.shield_contact {
+            ShieldContact::Bounce => {
+                if let Some(mut velocity) = velocity {
+                    trace!(?obstacle, "Shield bounced obstacle");
+                    velocity.0 -= 2.0 * relative_velocity.dot(away) * away;
+                }
+            }
+            ShieldContact::Hit { damage } if is_asteroid => {
+                commands.trigger_targets(
+                    AsteroidHitEvent {
+                        position: transform.translation,
+                        players: vec![player],
+                        direction: away,
+                        damage,
+                    },
+                    *obstacle,
+                );
+            }
+            ShieldContact::Hit { damage } => {
+                commands.trigger_targets(
+                    EnemyHitEvent {
+                        players: vec![player],
+                        damage,
+                    },
+                    *obstacle,
+                );
+            }
+        }
+    }
+
+    if shield.energy == 0.0 {
+        commands.entity(player).remove::<ShieldActive>();
+    }
+}
@@ -1,11 +1,11 @@
-use core::f32::consts::TAU;
+use core::{f32::consts::TAU, time::Duration};
 
 use avian2d::prelude::*;
 use bevy::prelude::*;
 use bevy_persistent::Persistent;
 use bevy_turborand::{DelegatedRng, RngComponent};
 use bevy_tweening::{
-    lens::{ColorMaterialColorLens, TransformPositionLens},
+    lens::{ColorMaterialColorLens, SpriteColorLens},
     *,
 };
 use itertools::Itertools;
@@ -13,22 +13,30 @@ use leafwing_input_manager::prelude::*;
 
 use crate::{
     assets::{
-        game_assets::PlayerSpriteSheet, EntitySpriteSheetCommands, GameAreaSettings,
-        GameStartSettings, HighScoreBoard, HighScoreKey, InputKeySettings, PlayerSettings,
+        EndlessHighScoreBoard, EntitySpriteSheetCommands, FlightModel, GameAreaSettings, GameMode,
+        GameStartSettings, HighScoreBoard, HighScoreKey, InputKeySettings, JumpMalfunctionEffect,
+        PlayerSettings, PlayerTextureCollection,
     },
     asteroid::AsteroidSprite,
     constants::PLAYER_Z_POS,
     movement::{ClampMovementSpeed, PauseMovement, PausedLinearVelocity, Wrapping},
     player::{
-        input::PlayerAction, Accelerating, AddToScoreEvent, Dead, EquippedWeapon, Jumping, NewLife,
-        Player, PlayerDeadEvent, PlayerFireEvent, PlayerJumpFinishedEvent, PlayerJumpingEvent,
-        PlayerSprite, Score, Turning,
+        input::PlayerAction, shield::ShieldSprite, Accelerating, AddToScoreEvent, Dead,
+        EquippedWeapon, Firing, Invulnerable, JumpDrive, Jumping, Kills, NewLife, OutOfLives,
+        Player, PlayerDeadEvent, PlayerFireEvent, PlayerInvulnerabilityFinishedEvent,
+        PlayerJumpFinishedEvent, PlayerJumpingEvent, PlayerSprite, Score, Shield, ShieldActive,
+        ShieldContactEvent, Turning,
     },
     projectile::SpawnProjectilesEvent,
-    tween_events::TweenCompletedEvent,
     CollisionLayer, GameState, PlayState, PlayingField,
 };
 
+/// Number of random destinations checked for asteroids, before landing anywhere.
+const SAFE_LANDING_ATTEMPTS: usize = 32;
+
+/// Horizontal distance between the spawn positions of local players.
+const PLAYER_SPAWN_SPACING: f32 = 120.0;
+
 // region: general systems
 
 pub fn player_acceleration_and_turning(
@@ -42,6 +50,10 @@ pub fn player_acceleration_and_turning(
     )>,
     time: Res<Time>,
 ) {
+    if player_settings.flight_model != FlightModel::Arcade {
+        return;
+    }
+
     // Acceleration => Velocity
     for (mut velocity, rotation) in q.p0().iter_mut() {
         let a = Vec2::new(rotation.cos, rotation.sin)
@@ -73,10 +85,106 @@ pub fn player_acceleration_and_turning(
     }
 }
 
+/// Applies thrust and turning as force and torque, for the [FlightModel::Newtonian] handling.
+pub fn apply_newtonian_thrust(
+    player_settings: Res<PlayerSettings>,
+    mut query: Query<
+        (
+            &mut ExternalForce,
+            &mut ExternalTorque,
+            &Rotation,
+            Has<Accelerating>,
+            Option<&Turning>,
+        ),
+        (With<Player>, Without<Jumping>),
+    >,
+) {
+    let FlightModel::Newtonian {
+        thrust,
+        angular_thrust,
+        ..
+    } = player_settings.flight_model
+    else {
+        return;
+    };
+
+    for (mut force, mut torque, rotation, accelerating, turning) in query.iter_mut() {
+        if accelerating {
+            force.apply_force(*rotation * Vec2::X * thrust);
+        }
+        match turning {
+            Some(Turning::Left(..)) => {
+                torque.apply_torque(angular_thrust);
+            }
+            Some(Turning::Right(..)) => {
+                torque.apply_torque(-angular_thrust);
+            }
+            None => {}
+        }
+    }
+}
+
+pub fn update_jump_cooldown(mut jump_drive_query: Query<&mut JumpDrive>, time: Res<Time>) {
+    for mut jump_drive in jump_drive_query.iter_mut() {
+        jump_drive.cooldown.tick(time.delta());
+    }
+}
+
+/// Moves jumping ships towards their destination, finishing the jump once they have arrived.
+pub fn move_jumping_players(
+    mut player_query: Query<(Entity, &mut Jumping, &mut Transform), With<Player>>,
+    time: Res<Time>,
+    mut commands: Commands,
+) {
+    for (player, mut jumping, mut transform) in player_query.iter_mut() {
+        jumping.timer.tick(time.delta());
+        let progress = quintic_in_out(jumping.timer.fraction());
+        transform.translation = jumping.start.lerp(jumping.destination, progress);
+        if jumping.timer.just_finished() {
+            commands.trigger_targets(PlayerJumpFinishedEvent, player);
+        }
+    }
+}
+
+/// Ends the invulnerability of the players whose time is up.
+pub fn update_invulnerability(
+    mut player_query: Query<(Entity, &mut Invulnerable), With<Player>>,
+    time: Res<Time>,
+    mut commands: Commands,
+) {
+    for (player, mut invulnerable) in player_query.iter_mut() {
+        if invulnerable.tick(time.delta()).just_finished() {
+            commands.trigger_targets(PlayerInvulnerabilityFinishedEvent, player);
+        }
+    }
+}
+
+/// Eases in and out of a jump, like [EaseFunction::QuinticInOut].
+fn quintic_in_out(progress: f32) -> f32 {
+    if progress < 0.5 {
+        16.0 * progress.powi(5)
+    } else {
+        1.0 - (2.0 - 2.0 * progress).powi(5) / 2.0
+    }
+}
+
 pub fn detect_player_collisions(
-    player_collision_query: Query<(&ColliderParent, &CollidingEntities), With<PlayerSprite>>,
-    player_query: Query<&Transform, (With<Player>, Without<Jumping>, Without<Dead>)>,
-    asteroid_query: Query<&Parent>,
+    // while the shield is raised, its sensor touches obstacles before the ship does
+    player_collision_query: Query<
+        (&ColliderParent, &CollidingEntities),
+        Or<(With<PlayerSprite>, With<ShieldSprite>)>,
+    >,
+    player_query: Query<
+        (&Transform, Has<ShieldActive>),
+        (
+            With<Player>,
+            Without<Jumping>,
+            Without<Dead>,
+            Without<Invulnerable>,
+        ),
+    >,
+    collider_parent_query: Query<&ColliderParent>,
+    layers_query: Query<&CollisionLayers>,
     mut commands: Commands,
 ) {
     // collect all player collisions by player (in case of multiple players)
@@ -86,7 +194,17 @@ pub fn detect_player_collisions(
         .fold(vec![], |mut acc, _key, (_, collisions)| {
             let asteroids = collisions
                 .iter()
-                .filter_map(|entity| asteroid_query.get(*entity).ok().map(|p| p.get()));
+                // hits by other players are credited to the shooter, see `detect_player_hits`
+                .filter(|entity| {
+                    !layers_query
+                        .get(**entity)
+                        .is_ok_and(|layers| layers.memberships.has_all(CollisionLayer::RivalLaser))
+                })
+                // the rigid body owning the collider, e.g. the projectile rather than its sprite
+                .filter_map(|entity| collider_parent_query.get(*entity).ok().map(|p| p.get()))
+                // touching both the shield and the ship
+                .filter(|obstacle| !acc.contains(obstacle))
+                .collect_vec();
             acc.extend(asteroids);
             acc
         });
@@ -102,7 +220,7 @@ pub fn detect_player_collisions(
             // with players that fullfill our criteria
             player_query
                 .get(player)
-                .map(|transform| (player, transform, asteroids))
+                .map(|(transform, shielded)| (player, transform, shielded, asteroids))
                 .ok()
         })
         .collect_vec();
@@ -110,13 +228,23 @@ pub fn detect_player_collisions(
         return;
     }
 
-    for (player, transform, asteroids) in player_collisions {
+    for (player, transform, shielded, asteroids) in player_collisions {
         debug!(
             ?player,
             ?asteroids,
+            shielded,
             position=?transform.translation,
             "Player collisions"
         );
+        if shielded {
+            commands.trigger_targets(
+                ShieldContactEvent {
+                    obstacles: asteroids,
+                },
+                player,
+            );
+            continue;
+        }
         asteroids.iter().for_each(|asteroid| {
             debug!(?asteroid, "clearing colliding asteroid");
             commands.entity(*asteroid).despawn_recursive();
@@ -136,7 +264,16 @@ pub fn stop_accelerating(players: Query<Entity, With<Player>>, mut commands: Com
     }
 }
 
-pub fn resume_player_movement(mut commands: Commands, query: Query<Entity, With<Player>>) {
+pub fn stop_firing(players: Query<Entity, With<Firing>>, mut commands: Commands) {
+    for player in players.iter() {
+        commands.entity(player).remove::<Firing>();
+    }
+}
+
+pub fn resume_player_movement(
+    mut commands: Commands,
+    query: Query<Entity, (With<Player>, Without<OutOfLives>)>,
+) {
     for entity in query.iter() {
         commands.entity(entity).remove::<PauseMovement>();
     }
@@ -154,143 +291,317 @@ pub fn spawn_new_player(
     mut rand_query: Query<&mut RngComponent, With<PlayingField>>,
     game_start_settings: Res<GameStartSettings>,
     player_settings: Res<PlayerSettings>,
-    spritesheet_asset: PlayerSpriteSheet,
+    player_spritesheets: Res<PlayerTextureCollection>,
     input_assets: Res<InputKeySettings>,
     mut commands: Commands,
     #[cfg(feature = "dbg_colliders")] gamestate: Res<State<GameState>>,
 ) {
-    let mut rand = RngComponent::from(&mut rand_query.single_mut());
-    let position = Position::new(player_settings.spawn_position);
-    let facing_direction = Rotation::radians(rand.f32() * TAU);
-    let velocity = LinearVelocity(
-        (player_settings.speed_range.start
-            + (player_settings.speed_range.end - player_settings.speed_range.start) / 16.0)
-            * Vec2::from_angle(rand.f32_normalized() * TAU),
-    );
-    let input_manager_bundle = {
-        InputManagerBundle::with_map(InputMap::new([
-            (PlayerAction::Accelerate, input_assets.accelerate),
-            (PlayerAction::HyperJump, input_assets.jump),
-            (PlayerAction::TurnLeft, input_assets.rotate_left),
-            (PlayerAction::TurnRight, input_assets.rotate_right),
-            (PlayerAction::Fire, input_assets.fire),
-        ]))
-    };
-    let spritesheet = spritesheet_asset.spritesheet();
-    let clamp_speed = ClampMovementSpeed::new(player_settings.speed_range.clone());
-    let equipped_weapon = EquippedWeapon::new(game_start_settings.weapon_key.clone());
-    let score = Score::new(0);
-
-    commands
-        .entity(playing_field_query.single())
-        .with_children(|commands| {
-            let mut player = commands.spawn((
-                Name::new("Player"),
-                StateScoped(GameState::Playing),
-                Player {
-                    lives: game_start_settings.lives,
-                },
-                score,
-                equipped_weapon,
-                input_manager_bundle,
-                rand,
-                (
-                    // Rendering...
-                    SpatialBundle {
-                        transform: Transform::from_translation(Vec3::new(
-                            position.x,
-                            position.y,
-                            PLAYER_Z_POS,
-                        )),
-                        ..default()
-                    },
-                ),
-                (
-                    // Physics...
-                    RigidBody::Kinematic,
-                    position,
-                    facing_direction,
-                    velocity,
-                    PauseMovement,
-                ),
-                (
-                    // Movement restrictions...
-                    Wrapping,
-                    clamp_speed,
-                ),
-            ));
-
-            #[cfg(feature = "dbg_colliders")]
-            if *gamestate == GameState::DebugColliders {
-                player.insert(Accelerating);
-            }
+    let players = game_start_settings
+        .players
+        .clamp(1, GameStartSettings::MAX_PLAYERS)
+        .min(input_assets.players.len())
+        .min(game_start_settings.player_textures.len());
+    if players < game_start_settings.players {
+        warn!(
+            players,
+            requested = game_start_settings.players,
+            "Not enough key settings or ship textures for all players"
+        );
+    }
 
-            if player_settings.speed_decay > 0.0 {
-                player.insert(LinearDamping(player_settings.speed_decay));
+    for index in 0..players {
+        let keys = &input_assets.players[index];
+        let mut rand = RngComponent::from(&mut rand_query.single_mut());
+        let position = Position::new(player_settings.spawn_position + spawn_offset(index, players));
+        let facing_direction = Rotation::radians(rand.f32() * TAU);
+        let velocity = LinearVelocity(
+            (player_settings.speed_range.start
+                + (player_settings.speed_range.end - player_settings.speed_range.start) / 16.0)
+                * Vec2::from_angle(rand.f32_normalized() * TAU),
+        );
+        let input_manager_bundle = {
+            let mut input_map = InputMap::new([
+                (PlayerAction::Accelerate, keys.accelerate),
+                (PlayerAction::HyperJump, keys.jump),
+                (PlayerAction::TurnLeft, keys.rotate_left),
+                (PlayerAction::TurnRight, keys.rotate_right),
+                (PlayerAction::Fire, keys.fire),
+                (PlayerAction::Shield, keys.shield),
+            ]);
+            if let Some(aim) = &keys.aim {
+                input_map.insert_dual_axis(
+                    PlayerAction::AimJump,
+                    KeyboardVirtualDPad::new(aim.up, aim.down, aim.left, aim.right),
+                );
             }
+            InputManagerBundle::with_map(input_map)
+        };
+        let spritesheet = &player_spritesheets[&game_start_settings.player_textures[index]];
+        let clamp_speed = ClampMovementSpeed::new(player_settings.speed_range.clone());
+        let equipped_weapon = EquippedWeapon::new(game_start_settings.weapon_key.clone());
+        let score = Score::new(0);
+        let collides_with = match game_start_settings.mode {
+            GameMode::Campaign | GameMode::Endless | GameMode::TimeAttack => LayerMask::from([
+                CollisionLayer::Asteroids,
+                CollisionLayer::Enemy,
+                CollisionLayer::EnemyLaser,
+            ]),
+            GameMode::Versus => LayerMask::from([
+                CollisionLayer::Asteroids,
+                CollisionLayer::Enemy,
+                CollisionLayer::EnemyLaser,
+                CollisionLayer::RivalLaser,
+            ]),
+        };
 
-            player.with_children(|commands| {
-                commands
-                    .spawn(PlayerSprite)
-                    .insert_spritesheet(spritesheet, None, || {
-                        (
-                            PlayerSprite,
-                            CollisionLayers::new(
-                                [CollisionLayer::Player],
-                                [CollisionLayer::Asteroids],
-                            ),
-                        )
-                    });
+        commands
+            .entity(playing_field_query.single())
+            .with_children(|commands| {
+                let mut player = commands.spawn((
+                    Name::new(format!("Player {}", index + 1)),
+                    StateScoped(GameState::Playing),
+                    Player {
+                        index,
+                        lives: game_start_settings.starting_lives(),
+                    },
+                    score,
+                    Kills::default(),
+                    equipped_weapon,
+                    Shield {
+                        energy: player_settings.shield_capacity,
+                    },
+                    JumpDrive::charged(player_settings.max_jumps_per_life),
+                    input_manager_bundle,
+                    rand,
+                    (
+                        // Rendering...
+                        SpatialBundle {
+                            transform: Transform::from_translation(Vec3::new(
+                                position.x,
+                                position.y,
+                                PLAYER_Z_POS,
+                            )),
+                            ..default()
+                        },
+                    ),
+                    (
+                        // Physics...
+                        RigidBody::Kinematic,
+                        position,
+                        facing_direction,
+                        velocity,
+                        PauseMovement,
+                    ),
+                    (
+                        // Movement restrictions...
+                        Wrapping,
+                        clamp_speed,
+                    ),
+                ));
+
+                #[cfg(feature = "dbg_colliders")]
+                if *gamestate == GameState::DebugColliders {
+                    player.insert(Accelerating);
+                }
+
+                if player_settings.speed_decay > 0.0 {
+                    player.insert(LinearDamping(player_settings.speed_decay));
+                }
+
+                player.with_children(|commands| {
+                    commands
+                        .spawn(PlayerSprite)
+                        .insert_spritesheet(spritesheet, None, || {
+                            (
+                                PlayerSprite,
+                                Sensor,
+                                CollisionLayers::new([CollisionLayer::Player], collides_with),
+                            )
+                        });
+                });
+
+                debug!(player=?player.id(), index, "Spawned new player");
             });
+    }
+}
 
-            debug!(player=?player.id(), "Spawned new player");
-        });
+/// Switches the players between a kinematic and a dynamic body, as the level's
+/// [PlayerSettings::flight_model] requires.
+pub fn apply_flight_model(
+    player_query: Query<Entity, With<Player>>,
+    collider_query: Query<Entity, (With<PlayerSprite>, With<Collider>)>,
+    player_settings: Res<PlayerSettings>,
+    mut commands: Commands,
+) {
+    for player in player_query.iter() {
+        let mut player = commands.entity(player);
+        match player_settings.flight_model {
+            FlightModel::Arcade => {
+                player
+                    .insert((RigidBody::Kinematic, AngularVelocity::ZERO))
+                    .remove::<(ExternalForce, ExternalTorque, AngularDamping)>();
+            }
+            FlightModel::Newtonian {
+                angular_damping, ..
+            } => {
+                player.insert((
+                    RigidBody::Dynamic,
+                    ExternalForce::ZERO.with_persistence(false),
+                    ExternalTorque::ZERO.with_persistence(false),
+                    AngularDamping(angular_damping),
+                ));
+            }
+        }
+        debug!(player=?player.id(), flight_model=?player_settings.flight_model, "Flight model applied");
+    }
+
+    // sensors pass through everything, a dynamic ship is pushed around on contact
+    for collider in collider_query.iter() {
+        match player_settings.flight_model {
+            FlightModel::Arcade => {
+                commands.entity(collider).insert(Sensor);
+            }
+            FlightModel::Newtonian { .. } => {
+                commands.entity(collider).remove::<Sensor>();
+            }
+        }
+    }
 }
 
 pub fn reset_player_movement_system(
     mut commands: Commands,
-    mut player_query: Query<(Entity, &mut RngComponent), With<Player>>,
+    mut player_query: Query<(Entity, &Player, &mut RngComponent)>,
     player_settings: Res<PlayerSettings>,
 ) {
-    let (player_entity, mut rand) = player_query.single_mut();
-
-    let position = Position::new(player_settings.spawn_position);
-    let facing_direction = Rotation::radians(rand.f32() * TAU);
-    let velocity = PausedLinearVelocity(LinearVelocity(
-        player_settings.speed_range.start * Vec2::from_angle(rand.f32_normalized() * TAU),
-    ));
-    let clamp_movement = ClampMovementSpeed::new(player_settings.speed_range.clone());
-
-    debug!(player=?player_entity, "reset position,rotation and velocity for next level");
-    commands
-        .entity(player_entity)
-        .insert((position, facing_direction, velocity, clamp_movement));
+    let players = player_query.iter().count();
+    for (player_entity, player, mut rand) in player_query.iter_mut() {
+        let position =
+            Position::new(player_settings.spawn_position + spawn_offset(player.index, players));
+        let facing_direction = Rotation::radians(rand.f32() * TAU);
+        let velocity = PausedLinearVelocity(LinearVelocity(
+            player_settings.speed_range.start * Vec2::from_angle(rand.f32_normalized() * TAU),
+        ));
+        let clamp_movement = ClampMovementSpeed::new(player_settings.speed_range.clone());
+
+        debug!(player=?player_entity, "reset position,rotation and velocity for next level");
+        commands.entity(player_entity).insert((
+            position,
+            facing_direction,
+            velocity,
+            clamp_movement,
+        ));
+    }
 }
 
 pub fn clear_safe_radius(
     mut commands: Commands,
-    player_query: Query<(Entity, &Position, &Rotation), With<Player>>,
+    player_query: Query<(Entity, &Position, &Rotation), (With<Player>, Without<OutOfLives>)>,
     asteroid_query: Query<(&ColliderParent, &Collider, &Position, &Rotation), With<AsteroidSprite>>,
     player_settings: Res<PlayerSettings>,
 ) {
-    let (player_entity, &position, &rotation) = player_query.single();
-    let collider = Collider::circle(player_settings.safe_radius);
-
-    debug!(safe_radius = player_settings.safe_radius);
-    asteroid_query
-        .iter()
-        .filter(|(_, a_colider, &a_position, &a_rotation)| {
-            contact_query::intersection_test(
-                &collider, position, rotation, a_colider, a_position, a_rotation,
-            )
-            .unwrap_or(false)
-        })
-        .map(|(asteroid, _, _, _)| asteroid)
-        .for_each(|asteroid| {
-            debug!(?asteroid, "clearing asteroid within death radius");
-            commands.entity(asteroid.get()).despawn_recursive();
-        });
-    commands.entity(player_entity).remove::<Dead>();
+    for (player_entity, &position, &rotation) in player_query.iter() {
+        commands.entity(player_entity).remove::<Dead>();
+        // an invulnerable player gets the chance to fly away instead
+        if !player_settings.invulnerability_duration.is_zero() {
+            continue;
+        }
+        let collider = Collider::circle(player_settings.safe_radius);
+
+        debug!(safe_radius = player_settings.safe_radius);
+        asteroid_query
+            .iter()
+            .filter(|(_, a_colider, &a_position, &a_rotation)| {
+                contact_query::intersection_test(
+                    &collider, position, rotation, a_colider, a_position, a_rotation,
+                )
+                .unwrap_or(false)
+            })
+            .map(|(asteroid, _, _, _)| asteroid)
+            .for_each(|asteroid| {
+                debug!(?asteroid, "clearing asteroid within death radius");
+                commands.entity(asteroid.get()).despawn_recursive();
+            });
+    }
+}
+
+pub fn recharge_jump_drive(
+    mut jump_drive_query: Query<&mut JumpDrive, With<Player>>,
+    player_settings: Res<PlayerSettings>,
+) {
+    for mut jump_drive in jump_drive_query.iter_mut() {
+        *jump_drive = JumpDrive::charged(player_settings.max_jumps_per_life);
+    }
+}
+
+/// Makes the player ignore collisions for [PlayerSettings::invulnerability_duration], blinking
+/// the player sprite meanwhile.
+pub fn start_invulnerability(
+    mut commands: Commands,
+    sprite_query: Query<(Entity, &Parent, &Sprite), With<PlayerSprite>>,
+    player_settings: Res<PlayerSettings>,
+) {
+    let duration = player_settings.invulnerability_duration;
+    if duration.is_zero() {
+        return;
+    }
+    let interval = player_settings
+        .invulnerability_blink_interval
+        .max(Duration::from_millis(10))
+        .min(duration);
+
+    for (sprite, player, base_sprite) in sprite_query.iter() {
+        let visible = base_sprite.color;
+        let faded = visible.with_alpha(0.2);
+
+        // fade out and in until the final fade in completes the invulnerability
+        let blinks = ((duration - interval).as_secs_f32() / (2.0 * interval.as_secs_f32()))
+            .round()
+            .max(1.0) as u32;
+        let blink_tween = Tween::new(
+            EaseFunction::QuadraticInOut,
+            interval,
+            SpriteColorLens {
+                start: visible,
+                end: faded,
+            },
+        )
+        .with_repeat_strategy(RepeatStrategy::MirroredRepeat)
+        .with_repeat_count(RepeatCount::Finite(2 * blinks - 1));
+        let final_tween = Tween::new(
+            EaseFunction::QuadraticInOut,
+            interval,
+            SpriteColorLens {
+                start: faded,
+                end: visible,
+            },
+        );
+
+        debug!(player=?player.get(), ?duration, "Player invulnerable");
+        commands
+            .entity(player.get())
+            .insert(Invulnerable(Timer::new(duration, TimerMode::Once)));
+        // runs once the game does, see `resume_player_animations`
+        commands
+            .entity(sprite)
+            .insert(Animator::new(blink_tween.then(final_tween)).with_state(AnimatorState::Paused));
+    }
+}
+
+/// Stops the tweens of the ships, so e.g. the blinking keeps up with the paused invulnerability.
+pub fn pause_player_animations(
+    mut animator_query: Query<&mut Animator<Sprite>, With<PlayerSprite>>,
+) {
+    for mut animator in animator_query.iter_mut() {
+        animator.state = AnimatorState::Paused;
+    }
+}
+
+pub fn resume_player_animations(
+    mut animator_query: Query<&mut Animator<Sprite>, With<PlayerSprite>>,
+) {
+    for mut animator in animator_query.iter_mut() {
+        animator.state = AnimatorState::Playing;
+    }
 }
 
 // endregion
@@ -300,28 +611,48 @@ pub fn clear_safe_radius(
 pub fn update_player_score(
     mut add_score_event: EventReader<AddToScoreEvent>,
     mut highscores: ResMut<Persistent<HighScoreBoard>>,
+    mut endless_highscores: ResMut<Persistent<EndlessHighScoreBoard>>,
     highscore_key: Option<Res<HighScoreKey>>,
     mut score_query: Query<&mut Score>,
     game_start_settings: Res<GameStartSettings>,
     mut commands: Commands,
 ) {
+    let mut scored = false;
     for hit_evt in add_score_event.read() {
         let player = hit_evt.player;
-        let mut score = score_query.get_mut(player).unwrap();
+        // points scored by anything else than a player, e.g. an enemy shooting an asteroid
+        let Ok(mut score) = score_query.get_mut(player) else {
+            continue;
+        };
         let new_score = **score + hit_evt.score;
 
         // add new life
-        if (**score / game_start_settings.new_life_every)
-            != (new_score / game_start_settings.new_life_every)
+        let score_per_life = game_start_settings.score_per_life();
+        if game_start_settings.mode != GameMode::Versus
+            && (**score / score_per_life) != (new_score / score_per_life)
         {
             commands.trigger_targets(NewLife, player);
         }
 
         // update score
         **score = new_score;
+        scored = true;
+    }
 
-        // maybe update highscore key
-        if let Some(new_highscore_key) = highscores.add_score(*score, highscore_key.as_deref()) {
+    // local players share a single highscore entry, with their combined score; versus matches
+    // don't make it to the highscores
+    if scored {
+        let highscores: &mut HighScoreBoard = match game_start_settings.mode {
+            GameMode::Campaign | GameMode::TimeAttack => &mut highscores,
+            GameMode::Endless => &mut endless_highscores,
+            GameMode::Versus => return,
+        };
+        let score = Score::new(score_query.iter().map(|score| **score).sum());
+        if let Some(new_highscore_key) = highscores.add_score(
+            score,
+            game_start_settings.difficulty,
+            highscore_key.as_deref(),
+        ) {
             debug!(?score, "Highscore reached");
             commands.insert_resource(new_highscore_key);
         }
@@ -339,12 +670,30 @@ pub fn on_new_life(trigger: Trigger<NewLife>, mut player_query: Query<&mut Playe
 
 pub fn on_player_death(
     trigger: Trigger<PlayerDeadEvent>,
-    mut player: Query<&mut Player>,
+    mut player_query: Query<&mut Player>,
     mut next: ResMut<NextState<PlayState>>,
+    game_start_settings: Res<GameStartSettings>,
+    mut commands: Commands,
 ) {
-    let mut player = player.get_mut(trigger.entity()).unwrap();
+    let player_entity = trigger.entity();
+    let mut player = player_query.get_mut(player_entity).unwrap();
+    // a versus match is decided by kills and score, players respawn endlessly
+    if game_start_settings.mode == GameMode::Versus {
+        warn!(player = player.index, "Player shot down - Restart");
+        next.set(PlayState::StartAfterDeath);
+        return;
+    }
     player.lives -= 1;
     if player.lives == 0 {
+        // the player stays around, without a ship, for its score and the HUD
+        warn!(player = player.index, "Player out of lives");
+        commands
+            .entity(player_entity)
+            .insert((OutOfLives, PauseMovement))
+            .despawn_descendants();
+    }
+
+    if player_query.iter().all(|player| player.lives == 0) {
         warn!("Player dead - Game Over");
         next.set(PlayState::GameOver(crate::GameOverReason::PlayerDead));
     } else {
@@ -356,39 +705,120 @@ pub fn on_player_death(
 pub fn on_player_firing(
     trigger: Trigger<PlayerFireEvent>,
     weapon_query: Query<&EquippedWeapon>,
+    game_start_settings: Res<GameStartSettings>,
     mut projectile_events: EventWriter<SpawnProjectilesEvent>,
 ) {
     let player = trigger.entity();
     let weapon = (**weapon_query.get(player).unwrap()).clone();
     trace!(?player, "Firing");
-    projectile_events.send(SpawnProjectilesEvent { player, weapon });
+    let layers = match game_start_settings.mode {
+        GameMode::Campaign | GameMode::Endless | GameMode::TimeAttack => CollisionLayers::new(
+            [CollisionLayer::Laser],
+            [CollisionLayer::Asteroids, CollisionLayer::Enemy],
+        ),
+        GameMode::Versus => CollisionLayers::new(
+            [CollisionLayer::Laser, CollisionLayer::RivalLaser],
+            [
+                CollisionLayer::Asteroids,
+                CollisionLayer::Enemy,
+                CollisionLayer::Player,
+            ],
+        ),
+    };
+    projectile_events.send(SpawnProjectilesEvent {
+        shooter: player,
+        weapon,
+        layers,
+        direction: None,
+    });
 }
 
 pub fn on_player_jumping(
     trigger: Trigger<PlayerJumpingEvent>,
     mut commands: Commands,
-    mut query: Query<(&Transform, &mut RngComponent), With<Player>>,
+    mut query: Query<(&Transform, &mut RngComponent, &mut JumpDrive), With<Player>>,
+    spatial_query: SpatialQuery,
     game_area_settings: Res<GameAreaSettings>,
     player_settings: Res<PlayerSettings>,
 ) {
     let player = trigger.entity();
-    let (transform, mut rand) = query.get_mut(player).unwrap();
-
-    let destination = {
-        loop {
-            let game_area = &game_area_settings.game_area;
-
-            let min_x = game_area.min().x;
-            let max_x = game_area.max().x;
-            let x = min_x.lerp(max_x, rand.f32());
-
-            let min_y = game_area.min().y;
-            let max_y = game_area.max().y;
-            let y = min_y.lerp(max_y, rand.f32());
-
-            let destination = Vec3::new(x, y, PLAYER_Z_POS);
-            if destination.distance(transform.translation) > player_settings.minimum_jump_distance {
-                break destination;
+    let (transform, mut rand, mut jump_drive) = query.get_mut(player).unwrap();
+    let game_area = &game_area_settings.game_area;
+
+    if let Some(target) = trigger.event().destination {
+        if !player_settings.is_valid_jump_target(
+            game_area,
+            transform.translation.truncate(),
+            target,
+        ) {
+            debug!(?player, ?target, "Invalid jump target");
+            return;
+        }
+    }
+    if !jump_drive.cooldown.finished() {
+        trace!(?player, "Jump drive cooling down");
+        return;
+    }
+    if jump_drive.jumps_left == Some(0) {
+        trace!(?player, "No jumps left");
+        return;
+    }
+    jump_drive.cooldown = Timer::new(player_settings.jump_cooldown, TimerMode::Once);
+    jump_drive.jumps_left = jump_drive.jumps_left.map(|jumps| jumps - 1);
+
+    let malfunction = player_settings
+        .jump_malfunction
+        .filter(|malfunction| rand.f32() < malfunction.chance)
+        .map(|malfunction| malfunction.effect);
+
+    let destination = match (malfunction, trigger.event().destination) {
+        (Some(JumpMalfunctionEffect::Death), _) => {
+            warn!(?player, "Jump malfunction - ship destroyed");
+            commands.entity(player).insert(Dead);
+            commands.trigger_targets(PlayerDeadEvent {}, player);
+            return;
+        }
+        (Some(JumpMalfunctionEffect::ShortJump { max_distance }), _) => {
+            debug!(?player, max_distance, "Jump malfunction - short jump");
+            let offset = Vec2::from_angle(rand.f32() * TAU) * max_distance * rand.f32();
+            (transform.translation.truncate() + offset)
+                .clamp(game_area.min().truncate(), game_area.max().truncate())
+                .extend(PLAYER_Z_POS)
+        }
+        (None, Some(target)) => target.extend(PLAYER_Z_POS),
+        (None, None) => {
+            let filter =
+                SpatialQueryFilter::from_mask([CollisionLayer::Asteroids, CollisionLayer::Enemy]);
+            let landing_zone = Collider::circle(player_settings.safe_landing_radius);
+
+            let mut attempts = 0;
+            loop {
+                let x = game_area.min().x.lerp(game_area.max().x, rand.f32());
+                let y = game_area.min().y.lerp(game_area.max().y, rand.f32());
+
+                let destination = Vec3::new(x, y, PLAYER_Z_POS);
+                if destination.distance(transform.translation)
+                    <= player_settings.minimum_jump_distance
+                {
+                    continue;
+                }
+
+                // give up searching for a safe spot rather than not jumping at all
+                attempts += 1;
+                if player_settings.safe_landing_radius <= 0.0
+                    || attempts >= SAFE_LANDING_ATTEMPTS
+                    || spatial_query
+                        .shape_intersections(
+                            &landing_zone,
+                            destination.truncate(),
+                            0.0,
+                            filter.clone(),
+                        )
+                        .is_empty()
+                {
+                    trace!(attempts, "Found landing spot");
+                    break destination;
+                }
             }
         }
     };
@@ -405,24 +835,25 @@ pub fn on_player_jumping(
     .with_repeat_strategy(RepeatStrategy::MirroredRepeat)
     .with_repeat_count(RepeatCount::Finite(2));
 
-    // setup position animation
-    let pos_tween = Tween::new(
-        EaseFunction::QuinticInOut,
-        player_settings.jump_animation_duration,
-        TransformPositionLens {
-            start: transform.translation,
-            end: destination,
-        },
-    )
-    .with_completed_event(TweenCompletedEvent::JumpFinished.ordinal());
-
     debug!(?player, ?destination, "Player jumping");
     commands
         .entity(player)
-        .insert(Jumping)
+        .insert(Jumping {
+            timer: Timer::new(player_settings.jump_animation_duration, TimerMode::Once),
+            start: transform.translation,
+            destination,
+        })
         .insert(PauseMovement)
-        .insert(AssetAnimator::new(color_tween))
-        .insert(Animator::new(pos_tween));
+        .insert(AssetAnimator::new(color_tween));
+}
+
+pub fn on_player_invulnerability_finished(
+    trigger: Trigger<PlayerInvulnerabilityFinishedEvent>,
+    mut commands: Commands,
+) {
+    let player = trigger.entity();
+    commands.entity(player).remove::<Invulnerable>();
+    debug!(?player, "invulnerability finished");
 }
 
 pub fn on_player_jump_finished(trigger: Trigger<PlayerJumpFinishedEvent>, mut commands: Commands) {
@@ -435,3 +866,15 @@ pub fn on_player_jump_finished(trigger: Trigger<PlayerJumpFinishedEvent>, mut co
 }
 
 // endregion
+
+// region: general functions
+
+/// Spreads the spawn positions of local players horizontally around the configured position.
+fn spawn_offset(index: usize, players: usize) -> Vec2 {
+    Vec2::new(
+        (index as f32 - (players as f32 - 1.0) / 2.0) * PLAYER_SPAWN_SPACING,
+        0.0,
+    )
+}
+
+// endregion
@@ -0,0 +1,109 @@
+use avian2d::prelude::*;
+use bevy::{prelude::*, utils::HashSet};
+
+use crate::{
+    assets::{GameStartSettings, PlayerSettings},
+    levels::versus_mode,
+    player::{
+        shield::ShieldSprite, update_player_score, AddToScoreEvent, Dead, Invulnerable, Jumping,
+        Kills, Player, PlayerDeadEvent, PlayerSet, PlayerSprite, Score, Shield, ShieldActive,
+    },
+    projectile::ProjectileCollisionEvent,
+    states::{GameOverReason, PlayState},
+};
+
+pub fn init_versus(app: &mut App) {
+    app.add_systems(
+        FixedPostUpdate,
+        (
+            detect_player_hits.before(update_player_score),
+            detect_match_won.after(update_player_score),
+        )
+            .run_if(in_state(PlayState::Running))
+            .run_if(versus_mode)
+            .in_set(PlayerSet),
+    );
+}
+
+/// Shoots down players hit by the projectiles or beams of another player, awarding the
+/// [crate::assets::VersusSettings::kill_points] to the shooter.
+///
+/// An active shield absorbs the hit, at the cost of some energy.
+#[allow(clippy::too_many_arguments)]
+fn detect_player_hits(
+    mut projectile_hit_events: EventReader<ProjectileCollisionEvent>,
+    collider_query: Query<&ColliderParent, Or<(With<PlayerSprite>, With<ShieldSprite>)>>,
+    mut victim_query: Query<
+        (&mut Shield, Has<ShieldActive>),
+        (
+            With<Player>,
+            Without<Jumping>,
+            Without<Dead>,
+            Without<Invulnerable>,
+        ),
+    >,
+    mut kills_query: Query<&mut Kills>,
+    game_start_settings: Res<GameStartSettings>,
+    player_settings: Res<PlayerSettings>,
+    mut score_events: EventWriter<AddToScoreEvent>,
+    mut commands: Commands,
+) {
+    let mut shot_down = HashSet::new();
+    for event in projectile_hit_events.read() {
+        let Ok(victim) = collider_query
+            .get(event.entity_hit)
+            .map(|parent| parent.get())
+        else {
+            continue;
+        };
+        let shooter = event.shot_by_player;
+        // enemy fire is handled by the regular player collisions
+        if victim == shooter || !kills_query.contains(shooter) || shot_down.contains(&victim) {
+            continue;
+        }
+        let Ok((mut shield, shielded)) = victim_query.get_mut(victim) else {
+            continue;
+        };
+
+        if shielded {
+            trace!(?victim, ?shooter, "Shield absorbed rival fire");
+            shield.energy = (shield.energy - player_settings.shield_hit_cost).max(0.0);
+            if shield.energy == 0.0 {
+                commands.entity(victim).remove::<ShieldActive>();
+            }
+            continue;
+        }
+
+        debug!(?victim, ?shooter, "Player shot down by a rival");
+        shot_down.insert(victim);
+        **kills_query.get_mut(shooter).unwrap() += 1;
+        score_events.send(AddToScoreEvent {
+            player: shooter,
+            score: game_start_settings.versus.kill_points,
+        });
+        commands.entity(victim).insert(Dead);
+        commands.trigger_targets(PlayerDeadEvent {}, victim);
+    }
+}
+
+/// Ends the match as soon as a player reaches the kill or score limit.
+fn detect_match_won(
+    player_query: Query<(&Player, &Kills, &Score)>,
+    game_start_settings: Res<GameStartSettings>,
+    mut next: ResMut<NextState<PlayState>>,
+) {
+    let versus = &game_start_settings.versus;
+    let winner = player_query.iter().find(|(_, kills, score)| {
+        versus.kill_limit.is_some_and(|limit| ***kills >= limit)
+            || versus.score_limit.is_some_and(|limit| ***score >= limit)
+    });
+    if let Some((player, kills, score)) = winner {
+        info!(
+            player = player.index,
+            kills = **kills,
+            score = **score,
+            "won the match!"
+        );
+        next.set(PlayState::GameOver(GameOverReason::MatchWon));
+    }
+}
@@ -0,0 +1,191 @@
+use avian2d::prelude::*;
+use bevy::{audio::Volume, prelude::*, sprite::Anchor};
+
+use crate::{
+    assets::{
+        AmmonitionDepot, AmmonitionKind, AmmonitionTextureCollection, EntitySpriteSheetCommands,
+        SpriteSheetAsset,
+    },
+    constants::BEAM_RELATIVE_Z_POS,
+    player::{Firing, Jumping},
+    projectile::{Beam, BeamSprite, ProjectileCollisionEvent, ProjectileSet, SpawnBeamEvent},
+    rollback::RollbackApp,
+    states::PlayState,
+};
+
+pub fn init_beams(app: &mut App) {
+    app.add_systems(
+        FixedPostUpdate,
+        (despawn_released_beams, update_beams)
+            .chain()
+            .run_if(in_state(PlayState::Running))
+            .in_set(ProjectileSet),
+    )
+    .add_systems(
+        OnExit(PlayState::Running),
+        despawn_all_beams.in_set(ProjectileSet),
+    )
+    .observe(on_beam_spawn)
+    .rollback_component::<Beam>()
+    .rollback_component::<BeamSprite>();
+}
+
+fn on_beam_spawn(
+    trigger: Trigger<SpawnBeamEvent>,
+    beam_query: Query<(&Beam, &Parent)>,
+    ammonition_depot: Res<AmmonitionDepot>,
+    ammonition_spritesheets: Res<AmmonitionTextureCollection>,
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+) {
+    let event = trigger.event();
+    let shooter = trigger.entity();
+
+    // the beam keeps firing for as long as fire is held, so it only needs spawning once
+    if beam_query
+        .iter()
+        .any(|(beam, parent)| parent.get() == shooter && beam.port == event.port)
+    {
+        return;
+    }
+
+    let ammonition_info = &ammonition_depot[&event.ammonition];
+    let AmmonitionKind::Beam { hit_interval } = ammonition_info.kind else {
+        warn!(ammonition = event.ammonition, "Ammonition is not a beam");
+        return;
+    };
+    let ammonition_gfx = &ammonition_spritesheets[ammonition_info.texture_key.as_str()];
+    let Some(size) = ammonition_gfx.size else {
+        warn!(
+            texture_key = ammonition_info.texture_key,
+            "Beam textures must have a size"
+        );
+        return;
+    };
+    // beams use ray casts instead of colliders
+    let ammonition_gfx = SpriteSheetAsset {
+        physics: None,
+        ..ammonition_gfx.clone()
+    };
+
+    let mut hit_timer = Timer::new(hit_interval, TimerMode::Repeating);
+    // hit immediately when the beam is turned on
+    hit_timer.set_elapsed(hit_interval);
+
+    commands.entity(shooter).with_children(|children| {
+        let mut beam = children.spawn((
+            Name::new("Beam"),
+            StateScoped(event.state),
+            Beam {
+                shot_by_player: shooter,
+                ammonition: event.ammonition.clone(),
+                port: event.port,
+                offset: event.position,
+                direction: event.direction,
+                size,
+                layers: event.layers,
+                damage: ammonition_info.damage,
+                hit_timer,
+            },
+            SpatialBundle {
+                transform: Transform::from_translation(event.position.extend(BEAM_RELATIVE_Z_POS))
+                    .with_rotation(Quat::from_rotation_z(event.direction.as_radians())),
+                ..default()
+            },
+        ));
+
+        if let Some(audio) = &event.audio {
+            beam.insert(AudioBundle {
+                source: asset_server.load(audio),
+                settings: PlaybackSettings::LOOP
+                    .with_spatial(true)
+                    .with_volume(Volume::new(0.6)),
+            });
+        }
+
+        beam.with_children(|beam_children| {
+            beam_children
+                .spawn(BeamSprite)
+                .insert_spritesheet(&ammonition_gfx, None, || ());
+        });
+
+        debug!(beam=?beam.id(), "Spawned beam");
+    });
+}
+
+/// Casts a ray along every beam, stretching the beam sprite up to the first asteroid hit, and
+/// applies hits at the rate configured for the ammonition.
+fn update_beams(
+    time: Res<Time>,
+    spatial_query: SpatialQuery,
+    mut beam_query: Query<(&mut Beam, &Parent, &Children)>,
+    shooter_query: Query<(&Position, &Rotation)>,
+    collider_parent_query: Query<&ColliderParent>,
+    mut sprite_query: Query<&mut Sprite, With<BeamSprite>>,
+    mut events: EventWriter<ProjectileCollisionEvent>,
+) {
+    for (mut beam, shooter, children) in beam_query.iter_mut() {
+        let Ok((position, rotation)) = shooter_query.get(shooter.get()) else {
+            continue;
+        };
+        let origin = position.0 + *rotation * beam.offset;
+        let Ok(direction) = Dir2::new((*rotation * beam.direction) * Vec2::X) else {
+            continue;
+        };
+
+        // the beam must not hit the ship emitting it
+        let hit = spatial_query.cast_ray_predicate(
+            origin,
+            direction,
+            beam.size.y,
+            true,
+            SpatialQueryFilter::from_mask(beam.layers.filters),
+            &|entity| {
+                collider_parent_query
+                    .get(entity)
+                    .map_or(true, |parent| parent.get() != shooter.get())
+            },
+        );
+        let length = hit.as_ref().map_or(beam.size.y, |hit| hit.time_of_impact);
+
+        // beam textures point upwards, like the projectiles, and are rotated by the sprite sheet
+        for child in children.iter() {
+            if let Ok(mut sprite) = sprite_query.get_mut(*child) {
+                sprite.anchor = Anchor::BottomCenter;
+                sprite.custom_size = Some(Vec2::new(beam.size.x, length));
+            }
+        }
+
+        if beam.hit_timer.tick(time.delta()).just_finished() {
+            if let Some(hit) = hit {
+                trace!(entity_hit=?hit.entity, "Beam hit");
+                events.send(ProjectileCollisionEvent {
+                    entity_hit: hit.entity,
+                    shot_by_player: beam.shot_by_player,
+                    ammonition: beam.ammonition.clone(),
+                    direction: *direction,
+                    damage: beam.damage,
+                });
+            }
+        }
+    }
+}
+
+fn despawn_released_beams(
+    beam_query: Query<(Entity, &Parent), With<Beam>>,
+    shooter_query: Query<(), (With<Firing>, Without<Jumping>)>,
+    mut commands: Commands,
+) {
+    for (beam, shooter) in beam_query.iter() {
+        if shooter_query.get(shooter.get()).is_err() {
+            trace!(?beam, "Despawning released beam");
+            commands.entity(beam).despawn_recursive();
+        }
+    }
+}
+
+fn despawn_all_beams(beam_query: Query<Entity, With<Beam>>, mut commands: Commands) {
+    for beam in beam_query.iter() {
+        commands.entity(beam).despawn_recursive();
+    }
+}
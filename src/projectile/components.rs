@@ -1,10 +1,34 @@
+use avian2d::prelude::*;
 use bevy::prelude::*;
 
-#[derive(Component, Reflect)]
+#[derive(Component, Reflect, Clone)]
 pub struct Projectile {
     pub timer: Timer,
     pub shot_by_player: Entity,
+    pub ammonition: String,
+    pub damage: u32,
 }
 
-#[derive(Component)]
+#[derive(Component, Clone)]
 pub struct ProjectileSprite;
+
+/// A continuous beam, fired from one of the weapon ports of its parent.
+#[derive(Component, Clone)]
+pub struct Beam {
+    pub shot_by_player: Entity,
+    pub ammonition: String,
+    /// index of the weapon port firing the beam
+    pub port: usize,
+    /// position of the weapon port, relative to the shooter
+    pub offset: Vec2,
+    /// direction of the weapon port, relative to the shooter
+    pub direction: Rotation,
+    /// width and maximum length of the beam
+    pub size: Vec2,
+    pub layers: CollisionLayers,
+    pub damage: u32,
+    pub hit_timer: Timer,
+}
+
+#[derive(Component, Clone)]
+pub struct BeamSprite;
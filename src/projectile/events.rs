@@ -5,8 +5,12 @@ use crate::states::GameState;
 
 #[derive(Event)]
 pub struct SpawnProjectilesEvent {
-    pub player: Entity,
+    pub shooter: Entity,
     pub weapon: String,
+    /// collision layers of the fired projectiles
+    pub layers: CollisionLayers,
+    /// fire in this direction instead of the direction the shooter is facing
+    pub direction: Option<Rotation>,
 }
 
 #[derive(Event)]
@@ -14,6 +18,23 @@ pub struct SpawnSingleProjectileEvent {
     pub state: GameState,
     pub position: Vec3,
     pub direction: Rotation,
+    /// velocity of the shooter when firing
+    pub shooter_velocity: LinearVelocity,
+    pub layers: CollisionLayers,
+    pub ammonition: String,
+    pub audio: Option<String>,
+}
+
+#[derive(Event)]
+pub struct SpawnBeamEvent {
+    pub state: GameState,
+    /// index of the weapon port firing the beam
+    pub port: usize,
+    /// position of the weapon port, relative to the shooter
+    pub position: Vec2,
+    /// direction of the weapon port, relative to the shooter
+    pub direction: Rotation,
+    pub layers: CollisionLayers,
     pub ammonition: String,
     pub audio: Option<String>,
 }
@@ -23,6 +44,12 @@ pub struct SpawnSingleProjectileEvent {
 pub struct ProjectileCollisionEvent {
     /// The entity that was hit by a projectile
     pub entity_hit: Entity,
-    /// Player entities that shot the one of the projectiles that hit `entity_hit`
+    /// The entity that shot the projectile that hit `entity_hit`, usually a player
     pub shot_by_player: Entity,
+    /// The ammonition of the projectile
+    pub ammonition: String,
+    /// Normalized direction the projectile was traveling in when hitting `entity_hit`
+    pub direction: Vec2,
+    /// Damage done by the projectile
+    pub damage: u32,
 }
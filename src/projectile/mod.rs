@@ -1,3 +1,4 @@
+mod beams;
 mod components;
 mod events;
 mod plugin;
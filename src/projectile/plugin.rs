@@ -2,10 +2,13 @@ use avian2d::prelude::*;
 use bevy::prelude::*;
 
 use crate::{
+    enemy::EnemySet,
     projectile::{
-        despawn_all_projectiles, detect_projetile_collision, on_projectile_spawn,
-        spawn_projectiles, timeout_projectiles, ProjectileCollisionEvent, SpawnProjectilesEvent,
+        beams::init_beams, despawn_all_projectiles, detect_projetile_collision,
+        on_projectile_spawn, spawn_projectiles, timeout_projectiles, Projectile,
+        ProjectileCollisionEvent, ProjectileSprite, SpawnProjectilesEvent,
     },
+    rollback::RollbackApp,
     states::GameState,
     PlayState,
 };
@@ -21,19 +24,28 @@ impl Plugin for ProjectilePlugin {
         dbg_colliders::setup_dbg(app);
 
         app.add_event::<SpawnProjectilesEvent>()
-            .add_event::<ProjectileCollisionEvent>();
+            .add_event::<ProjectileCollisionEvent>()
+            .rollback_component::<Projectile>()
+            .rollback_component::<ProjectileSprite>();
 
         app.add_systems(
-            Update,
-            (
-                spawn_projectiles.run_if(on_event::<SpawnProjectilesEvent>()),
-                detect_projetile_collision.run_if(on_event::<CollisionStarted>()),
-            )
+            FixedUpdate,
+            // enemies fire during the same tick
+            spawn_projectiles
+                .run_if(on_event::<SpawnProjectilesEvent>())
+                .after(EnemySet)
                 .run_if(in_state(PlayState::Running))
                 .in_set(ProjectileSet),
         )
         .add_systems(
-            Update,
+            FixedPostUpdate,
+            detect_projetile_collision
+                .run_if(on_event::<CollisionStarted>())
+                .run_if(in_state(PlayState::Running))
+                .in_set(ProjectileSet),
+        )
+        .add_systems(
+            FixedUpdate,
             (timeout_projectiles,)
                 .run_if(in_state(GameState::Playing))
                 .in_set(ProjectileSet),
@@ -46,6 +58,9 @@ impl Plugin for ProjectilePlugin {
             OnExit(PlayState::StartNewGame),
             despawn_all_projectiles.in_set(ProjectileSet),
         )
+        .configure_sets(FixedPostUpdate, ProjectileSet.after(PhysicsSet::Sync))
         .observe(on_projectile_spawn);
+
+        init_beams(app);
     }
 }
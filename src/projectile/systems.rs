@@ -4,17 +4,17 @@ use bevy_turborand::{DelegatedRng, RngComponent};
 
 use crate::{
     assets::{
-        AmmonitionDepot, AmmonitionSelection, AmmonitionTextureCollection,
-        EntitySpriteSheetCommands, WeaponCollection,
+        AmmonitionDepot, AmmonitionEffect, AmmonitionKind, AmmonitionSelection,
+        AmmonitionTextureCollection, EntitySpriteSheetCommands, WeaponCollection,
     },
-    movement::Wrapping,
-    player::Player,
+    asteroid::SurfaceMaterial,
+    movement::{Steering, Wrapping},
     projectile::{
-        Projectile, ProjectileCollisionEvent, ProjectileSprite, SpawnProjectilesEvent,
-        SpawnSingleProjectileEvent,
+        Projectile, ProjectileCollisionEvent, ProjectileSprite, SpawnBeamEvent,
+        SpawnProjectilesEvent, SpawnSingleProjectileEvent,
     },
     utils::RngComponentExt,
-    CollisionLayer, GameState, PlayingField,
+    GameState, PlayingField,
 };
 
 pub fn despawn_all_projectiles(
@@ -30,23 +30,36 @@ pub fn despawn_all_projectiles(
 pub fn spawn_projectiles(
     mut ev_spawn: EventReader<SpawnProjectilesEvent>,
     mut commands: Commands,
-    mut player_query: Query<(&Transform, &Rotation, &mut RngComponent), With<Player>>,
+    mut shooter_query: Query<(
+        &Transform,
+        &Rotation,
+        &mut RngComponent,
+        Option<&mut LinearVelocity>,
+    )>,
     weapon_collection: Res<WeaponCollection>,
     ammonition_depot: Res<AmmonitionDepot>,
 ) {
-    for SpawnProjectilesEvent { player, weapon } in ev_spawn.read() {
+    for SpawnProjectilesEvent {
+        shooter,
+        weapon,
+        layers,
+        direction: aim,
+    } in ev_spawn.read()
+    {
         let weapon_info = &weapon_collection[weapon];
 
-        let (player_transform, player_rotation, mut rand) = player_query.get_mut(*player).unwrap();
-        for weapon_port in weapon_info.weapon_ports.iter() {
-            let direction = Rotation::degrees(weapon_port.rotation) * *player_rotation;
-            debug!(?direction, "projectile direction");
-            let position = {
-                let port_rotation = Rotation::degrees(-90.0) * *player_rotation;
-                (player_transform.translation.truncate() + port_rotation * weapon_port.position)
-                    .extend(player_transform.translation.z)
-            };
+        let Ok((shooter_transform, shooter_rotation, mut rand, mut shooter_velocity_component)) =
+            shooter_query.get_mut(*shooter)
+        else {
+            continue;
+        };
+        let shooter_rotation = aim.unwrap_or(*shooter_rotation);
+        let shooter_velocity = shooter_velocity_component
+            .as_deref()
+            .copied()
+            .unwrap_or(LinearVelocity::ZERO);
 
+        for (port, weapon_port) in weapon_info.weapon_ports.iter().enumerate() {
             let ammonition = pick_random_ammonition_index(
                 &mut rand,
                 weapon_port
@@ -55,17 +68,48 @@ pub fn spawn_projectiles(
                     .unwrap_or(&weapon_info.default_ammonition),
                 &ammonition_depot,
             );
+
+            if let AmmonitionKind::Beam { .. } = ammonition_depot[&ammonition].kind {
+                commands.trigger_targets(
+                    SpawnBeamEvent {
+                        state: GameState::Playing,
+                        port,
+                        position: Rotation::degrees(-90.0) * weapon_port.position,
+                        direction: Rotation::degrees(weapon_port.rotation),
+                        layers: *layers,
+                        ammonition,
+                        audio: weapon_info.audio.clone(),
+                    },
+                    *shooter,
+                );
+                continue;
+            }
+
+            let direction = Rotation::degrees(weapon_port.rotation) * shooter_rotation;
+            debug!(?direction, "projectile direction");
+            let position = {
+                let port_rotation = Rotation::degrees(-90.0) * shooter_rotation;
+                (shooter_transform.translation.truncate() + port_rotation * weapon_port.position)
+                    .extend(shooter_transform.translation.z)
+            };
+
             commands.trigger_targets(
                 SpawnSingleProjectileEvent {
                     state: GameState::Playing,
                     position,
                     direction,
+                    shooter_velocity,
+                    layers: *layers,
                     ammonition,
                     audio: weapon_info.audio.clone(),
                 },
-                *player,
+                *shooter,
             );
         }
+
+        if let Some(velocity) = shooter_velocity_component.as_deref_mut() {
+            velocity.0 -= shooter_rotation * Vec2::X * weapon_info.recoil;
+        }
     }
 }
 
@@ -128,7 +172,10 @@ pub fn on_projectile_spawn(
     let ammonitio_gfx = &ammonition_spritesheets[ammonition_info.texture_key.as_str()];
 
     let direction = event.direction;
-    let velocity = LinearVelocity(direction * Vec2::X * ammonition_info.speed);
+    let velocity = LinearVelocity(
+        direction * Vec2::X * ammonition_info.speed
+            + event.shooter_velocity.0 * ammonition_info.inherit_velocity,
+    );
     let timeout = ammonition_info.timeout;
 
     commands.entity(playing_field).with_children(|children| {
@@ -138,6 +185,8 @@ pub fn on_projectile_spawn(
             Projectile {
                 timer: Timer::new(timeout, TimerMode::Once),
                 shot_by_player: trigger.entity(),
+                ammonition: event.ammonition.clone(),
+                damage: ammonition_info.damage,
             },
             Wrapping,
             RigidBody::Kinematic,
@@ -148,15 +197,15 @@ pub fn on_projectile_spawn(
             direction,
             velocity,
         ));
+        if let Some(steering) = &ammonition_info.steering {
+            projectile.insert(Steering::new(steering.clone()));
+        }
 
         projectile.with_children(|projectile_children| {
             projectile_children
                 .spawn(ProjectileSprite)
                 .insert_spritesheet(ammonitio_gfx, None, || {
-                    (
-                        ProjectileSprite,
-                        CollisionLayers::new([CollisionLayer::Laser], [CollisionLayer::Asteroids]),
-                    )
+                    (ProjectileSprite, Sensor, event.layers)
                 });
         });
         let projectile = projectile.id();
@@ -170,6 +219,8 @@ pub fn on_projectile_spawn(
                 Projectile {
                     timer: Timer::new(timeout, TimerMode::Once),
                     shot_by_player: trigger.entity(),
+                    ammonition: event.ammonition.clone(),
+                    damage: ammonition_info.damage,
                 },
                 Wrapping,
                 RigidBody::Kinematic,
@@ -192,14 +243,20 @@ pub fn on_projectile_spawn(
     });
 }
 
-/// System for detecting player collision with any collider as setup when the player is spawned.
+/// System for detecting projectile collision with any collider as setup when the projectile is
+/// spawned.
+///
+/// Projectiles ricochet off materials they can't damage, if configured so for the ammonition.
 pub fn detect_projetile_collision(
     mut commands: Commands,
     collision_query: Query<
         (&ColliderParent, &CollidingEntities),
         (With<ProjectileSprite>, Changed<CollidingEntities>),
     >,
-    projectile_query: Query<&Projectile>,
+    mut projectile_query: Query<(&Projectile, &Position, &mut LinearVelocity, &mut Rotation)>,
+    hit_query: Query<(&ColliderParent, &Position), Without<ProjectileSprite>>,
+    material_query: Query<&SurfaceMaterial>,
+    ammonition_depot: Res<AmmonitionDepot>,
     mut events: EventWriter<ProjectileCollisionEvent>,
 ) {
     for (projectile_entity, colliding_entities) in collision_query
@@ -211,16 +268,44 @@ pub fn detect_projetile_collision(
             ?colliding_entities,
             "Projectile collisions"
         );
-        let projectile = projectile_query.get(projectile_entity.get()).unwrap();
-
-        // we're only interested in the first collision
-        for entity in colliding_entities.iter().take(1) {
-            events.send(ProjectileCollisionEvent {
-                entity_hit: *entity,
-                shot_by_player: projectile.shot_by_player,
-            });
+        let (projectile, position, mut velocity, mut rotation) =
+            projectile_query.get_mut(projectile_entity.get()).unwrap();
+
+        // we're only interested in the first collision, other than with the shooter itself
+        let Some(entity) = colliding_entities.iter().find(|entity| {
+            hit_query.get(**entity).map_or(true, |(hit_parent, _)| {
+                hit_parent.get() != projectile.shot_by_player
+            })
+        }) else {
+            continue;
+        };
+
+        let hit = hit_query.get(*entity).ok();
+        let effect = hit
+            .and_then(|(hit_parent, _)| material_query.get(hit_parent.get()).ok())
+            .map(|material| ammonition_depot[&projectile.ammonition].effect_on(**material))
+            .unwrap_or_default();
+
+        if let (AmmonitionEffect::Ricochet, Some((_, hit_position))) = (effect, hit) {
+            // bounce off the surface, approximated by the center of the collider that was hit
+            let normal = (position.0 - hit_position.0).normalize_or_zero();
+            let speed_into_surface = velocity.dot(normal);
+            if speed_into_surface < 0.0 {
+                velocity.0 -= 2.0 * speed_into_surface * normal;
+                *rotation = Rotation::radians(velocity.y.atan2(velocity.x));
+            }
+            debug!(?projectile_entity, ?entity, "Projectile ricochet");
+            continue;
         }
 
+        events.send(ProjectileCollisionEvent {
+            entity_hit: *entity,
+            shot_by_player: projectile.shot_by_player,
+            ammonition: projectile.ammonition.clone(),
+            direction: velocity.normalize_or_zero(),
+            damage: projectile.damage,
+        });
+
         // get rid of the spent round!
         commands.entity(projectile_entity.get()).despawn_recursive();
     }
@@ -0,0 +1,375 @@
+//! Module for saving the state of the gameplay and going back to it later, so a network game can
+//! predict the inputs of the other player and correct the game once the real inputs arrive.
+//!
+//! The state of the gameplay is the [PlayingField] with all entities below it, restricted to the
+//! components registered with [RollbackApp::rollback_component], plus the resources registered
+//! with [RollbackApp::rollback_resource]. Plugins register the state they simulate, everything
+//! else, e.g. the UI, sounds or tweens, is left as it is when a [WorldSnapshot] is loaded.
+
+use core::any::{Any, TypeId};
+use std::collections::HashSet;
+
+use avian2d::{
+    collision::{collider::ColliderMarker, Collisions},
+    position::{PreSolveAccumulatedTranslation, PreSolveRotation, PreviousRotation},
+    prelude::*,
+    sync::{ancestor_marker::AncestorMarker, PreviousGlobalTransform},
+};
+use bevy::prelude::*;
+use bevy::{
+    audio::PlaybackSettings, ecs::component::ComponentId, sprite::Mesh2dHandle,
+    state::state_scoped::StateScoped,
+};
+use bevy_turborand::{GlobalRng, RngComponent};
+#[allow(unused)]
+use tracing::*;
+
+use crate::{
+    states::{GameState, PlayState},
+    PlayingField,
+};
+
+/// Observers react to restored components, e.g. by spawning effects, which is undone again until
+/// nothing changes anymore, at most this many times.
+const MAX_RESTORE_PASSES: usize = 4;
+
+type Value = Box<dyn Any + Send + Sync>;
+
+/// Plugin registering the engine and physics components used by the gameplay for rollback.
+pub struct RollbackPlugin;
+
+impl Plugin for RollbackPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<RollbackRegistry>()
+            .rollback_component::<Name>()
+            .rollback_component::<StateScoped<GameState>>()
+            .rollback_component::<StateScoped<PlayState>>()
+            .rollback_component::<Transform>()
+            .rollback_component::<GlobalTransform>()
+            .rollback_component::<Visibility>()
+            .rollback_component::<InheritedVisibility>()
+            .rollback_component::<ViewVisibility>()
+            .rollback_component::<Sprite>()
+            .rollback_component::<Handle<Image>>()
+            .rollback_component::<TextureAtlas>()
+            .rollback_component::<Mesh2dHandle>()
+            .rollback_component::<Handle<ColorMaterial>>()
+            .rollback_component::<Handle<AudioSource>>()
+            .rollback_component::<PlaybackSettings>()
+            .rollback_component::<RngComponent>()
+            .rollback_resource::<GlobalRng>();
+
+        app.rollback_component::<RigidBody>()
+            .rollback_component::<Position>()
+            .rollback_component::<Rotation>()
+            .rollback_component::<PreviousRotation>()
+            .rollback_component::<PreSolveAccumulatedTranslation>()
+            .rollback_component::<PreSolveRotation>()
+            .rollback_component::<AccumulatedTranslation>()
+            .rollback_component::<PreviousGlobalTransform>()
+            .rollback_component::<LinearVelocity>()
+            .rollback_component::<AngularVelocity>()
+            .rollback_component::<ExternalForce>()
+            .rollback_component::<ExternalTorque>()
+            .rollback_component::<ExternalImpulse>()
+            .rollback_component::<ExternalAngularImpulse>()
+            .rollback_component::<LinearDamping>()
+            .rollback_component::<AngularDamping>()
+            .rollback_component::<Restitution>()
+            .rollback_component::<Friction>()
+            .rollback_component::<GravityScale>()
+            .rollback_component::<LockedAxes>()
+            .rollback_component::<Dominance>()
+            .rollback_component::<Sleeping>()
+            .rollback_component::<TimeSleeping>()
+            .rollback_component::<Mass>()
+            .rollback_component::<InverseMass>()
+            .rollback_component::<Inertia>()
+            .rollback_component::<InverseInertia>()
+            .rollback_component::<CenterOfMass>()
+            .rollback_component::<Collider>()
+            .rollback_component::<ColliderMarker>()
+            .rollback_component::<ColliderParent>()
+            .rollback_component::<ColliderTransform>()
+            .rollback_component::<ColliderAabb>()
+            .rollback_component::<ColliderDensity>()
+            .rollback_component::<ColliderMassProperties>()
+            .rollback_component::<CollisionMargin>()
+            .rollback_component::<CollisionLayers>()
+            .rollback_component::<CollidingEntities>()
+            .rollback_component::<Sensor>()
+            .rollback_component::<SpeculativeMargin>()
+            .rollback_component::<AncestorMarker<RigidBody>>()
+            .rollback_component::<AncestorMarker<ColliderMarker>>()
+            .rollback_resource::<Collisions>();
+    }
+}
+
+/// Registering the state of the gameplay, see the [module](self) documentation.
+pub trait RollbackApp {
+    /// Saves and restores the component on the entities of the gameplay.
+    fn rollback_component<C: Component + Clone>(&mut self) -> &mut Self;
+
+    /// Saves and restores the resource, removing it if it didn't exist when saved.
+    fn rollback_resource<R: Resource + Clone>(&mut self) -> &mut Self;
+}
+
+impl RollbackApp for App {
+    fn rollback_component<C: Component + Clone>(&mut self) -> &mut Self {
+        let id = self.world_mut().init_component::<C>();
+        let mut registry = self
+            .world_mut()
+            .get_resource_or_insert_with(RollbackRegistry::default);
+        if !registry
+            .components
+            .iter()
+            .any(|component| component.id == id)
+        {
+            registry.components.push(ComponentRollback {
+                id,
+                save: save_component::<C>,
+                load: load_component::<C>,
+                remove: remove_component::<C>,
+            });
+        }
+        self
+    }
+
+    fn rollback_resource<R: Resource + Clone>(&mut self) -> &mut Self {
+        let id = TypeId::of::<R>();
+        let mut registry = self
+            .world_mut()
+            .get_resource_or_insert_with(RollbackRegistry::default);
+        if !registry.resources.iter().any(|resource| resource.id == id) {
+            registry.resources.push(ResourceRollback {
+                id,
+                save: save_resource::<R>,
+                load: load_resource::<R>,
+            });
+        }
+        self
+    }
+}
+
+/// The components and resources making up the state of the gameplay.
+#[derive(Resource, Debug, Default)]
+pub struct RollbackRegistry {
+    components: Vec<ComponentRollback>,
+    resources: Vec<ResourceRollback>,
+}
+
+#[derive(Debug)]
+struct ComponentRollback {
+    id: ComponentId,
+    save: fn(EntityRef) -> Option<Value>,
+    load: fn(&mut EntityWorldMut, &Value),
+    remove: fn(&mut EntityWorldMut),
+}
+
+#[derive(Debug)]
+struct ResourceRollback {
+    id: TypeId,
+    save: fn(&World) -> Option<Value>,
+    load: fn(&mut World, Option<&Value>),
+}
+
+fn save_component<C: Component + Clone>(entity: EntityRef) -> Option<Value> {
+    entity
+        .get::<C>()
+        .map(|component| Box::new(component.clone()) as Value)
+}
+
+fn load_component<C: Component + Clone>(entity: &mut EntityWorldMut, value: &Value) {
+    let Some(value) = value.downcast_ref::<C>() else {
+        return;
+    };
+    match entity.get_mut::<C>() {
+        Some(mut component) => *component = value.clone(),
+        None => {
+            entity.insert(value.clone());
+        }
+    }
+}
+
+fn remove_component<C: Component>(entity: &mut EntityWorldMut) {
+    entity.remove::<C>();
+}
+
+fn save_resource<R: Resource + Clone>(world: &World) -> Option<Value> {
+    world
+        .get_resource::<R>()
+        .map(|resource| Box::new(resource.clone()) as Value)
+}
+
+fn load_resource<R: Resource + Clone>(world: &mut World, value: Option<&Value>) {
+    match value.and_then(|value| value.downcast_ref::<R>()) {
+        Some(value) => match world.get_resource_mut::<R>() {
+            Some(mut resource) => *resource = value.clone(),
+            None => world.insert_resource(value.clone()),
+        },
+        None => {
+            world.remove_resource::<R>();
+        }
+    }
+}
+
+/// The state of the gameplay at one moment.
+pub struct WorldSnapshot {
+    /// parents before their children
+    entities: Vec<EntitySnapshot>,
+    /// in the order of [RollbackRegistry::resources]
+    resources: Vec<Option<Value>>,
+}
+
+struct EntitySnapshot {
+    entity: Entity,
+    /// the index in [RollbackRegistry::components] and the value of every saved component
+    components: Vec<(usize, Value)>,
+    children: Vec<Entity>,
+}
+
+impl core::fmt::Debug for WorldSnapshot {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("WorldSnapshot")
+            .field("entities", &self.entities.len())
+            .field("resources", &self.resources.len())
+            .finish()
+    }
+}
+
+impl WorldSnapshot {
+    /// Saves the current state of the gameplay.
+    pub fn save(world: &mut World) -> Self {
+        let entities = gameplay_entities(world);
+        let registry = world.resource::<RollbackRegistry>();
+        let entities = entities
+            .into_iter()
+            .map(|entity| {
+                let entity_ref = world.entity(entity);
+                EntitySnapshot {
+                    entity,
+                    components: registry
+                        .components
+                        .iter()
+                        .enumerate()
+                        .filter(|(_, component)| entity_ref.contains_id(component.id))
+                        .filter_map(|(index, component)| {
+                            (component.save)(entity_ref).map(|value| (index, value))
+                        })
+                        .collect(),
+                    children: entity_ref
+                        .get::<Children>()
+                        .map(|children| children.to_vec())
+                        .unwrap_or_default(),
+                }
+            })
+            .collect();
+        let resources = registry
+            .resources
+            .iter()
+            .map(|resource| (resource.save)(world))
+            .collect();
+        Self {
+            entities,
+            resources,
+        }
+    }
+
+    /// Puts the gameplay back into the saved state.
+    ///
+    /// Entities spawned since are despawned, despawned ones come back with the same [Entity] and
+    /// the saved components, unless their id is taken by another entity by now.
+    pub fn load(&self, world: &mut World) {
+        world.resource_scope(|world, registry: Mut<RollbackRegistry>| {
+            let saved = self
+                .entities
+                .iter()
+                .map(|saved| saved.entity)
+                .collect::<HashSet<_>>();
+
+            for _ in 0..MAX_RESTORE_PASSES {
+                let mut changed = false;
+                for entity in gameplay_entities(world) {
+                    if !saved.contains(&entity) && world.get_entity(entity).is_some() {
+                        world.entity_mut(entity).despawn_recursive();
+                        changed = true;
+                    }
+                }
+                for saved in self.entities.iter() {
+                    if world.get_entity(saved.entity).is_none() {
+                        if world.get_or_spawn(saved.entity).is_none() {
+                            warn!(entity = ?saved.entity, "Could not restore an entity, its id is taken");
+                            continue;
+                        }
+                        changed = true;
+                    }
+                    let mut entity = world.entity_mut(saved.entity);
+                    for (index, component) in registry.components.iter().enumerate() {
+                        let value = saved
+                            .components
+                            .iter()
+                            .find(|(saved_index, _)| *saved_index == index);
+                        match (entity.contains_id(component.id), value) {
+                            (false, Some((_, value))) => (component.load)(&mut entity, value),
+                            (true, None) => (component.remove)(&mut entity),
+                            (_, _) => continue,
+                        }
+                        changed = true;
+                    }
+                }
+                // lets observers react to the added and removed components
+                world.flush();
+                if !changed {
+                    break;
+                }
+            }
+
+            for saved in self.entities.iter() {
+                let Some(mut entity) = world.get_entity_mut(saved.entity) else {
+                    continue;
+                };
+                for (index, value) in saved.components.iter() {
+                    (registry.components[*index].load)(&mut entity, value);
+                }
+                let children = saved
+                    .children
+                    .iter()
+                    .copied()
+                    .filter(|child| world.get_entity(*child).is_some())
+                    .collect::<Vec<_>>();
+                let mut entity = world.entity_mut(saved.entity);
+                if children.is_empty() {
+                    entity.clear_children();
+                } else {
+                    entity.replace_children(&children);
+                }
+            }
+            world.flush();
+
+            for (resource, value) in registry.resources.iter().zip(self.resources.iter()) {
+                (resource.load)(world, value.as_ref());
+            }
+        });
+    }
+}
+
+/// The [PlayingField] and all entities below it, parents before their children.
+fn gameplay_entities(world: &mut World) -> Vec<Entity> {
+    let mut entities = world
+        .query_filtered::<Entity, With<PlayingField>>()
+        .iter(world)
+        .collect::<Vec<_>>();
+    let mut next = 0;
+    while let Some(entity) = entities.get(next).copied() {
+        if let Some(children) = world.get::<Children>(entity) {
+            let children = children
+                .iter()
+                .copied()
+                .filter(|child| world.get_entity(*child).is_some())
+                .collect::<Vec<_>>();
+            entities.extend(children);
+        }
+        next += 1;
+    }
+    entities
+}
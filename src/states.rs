@@ -26,6 +26,9 @@ pub enum GameState {
     DebugColliders,
     MainMenu,
     HighscoreMenu,
+    /// waiting for the other player of a network game
+    #[cfg(feature = "cmd_line")]
+    Connecting,
     Playing,
 }
 
@@ -51,6 +54,14 @@ pub enum PlayState {
 pub enum GameOverReason {
     PlayerDead,
     GameWon,
+    /// a player reached the kill or score limit of a versus match
+    MatchWon,
+    /// the time limit of a level ran out in time-attack mode
+    TimeUp,
+    /// the escort of a level was destroyed
+    EscortLost,
+    /// the other player of a network game left or stopped answering
+    Disconnected,
 }
 
 pub struct GameStatesPlugin;
@@ -1,5 +0,0 @@
-mod events;
-mod plugin;
-mod systems;
-
-pub use self::{events::*, plugin::*};
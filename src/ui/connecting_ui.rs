@@ -0,0 +1,66 @@
+use bevy::{
+    color::palettes::css,
+    input::{keyboard::KeyboardInput, ButtonState},
+    prelude::*,
+};
+
+use super::{
+    constants::{BUTTON_FONT_SIZE, H1_FONT_SIZE},
+    UiSet,
+};
+use crate::states::GameState;
+
+pub fn build_ui(app: &mut App) {
+    let state = GameState::Connecting;
+
+    app.add_systems(OnEnter(state), spawn_ui.in_set(UiSet))
+        .add_systems(Update, cancel_on_esc.run_if(in_state(state)).in_set(UiSet));
+}
+
+fn spawn_ui(mut commands: Commands) {
+    commands
+        .spawn((
+            StateScoped(GameState::Connecting),
+            NodeBundle {
+                style: Style {
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    display: Display::Flex,
+                    flex_direction: FlexDirection::Column,
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    ..default()
+                },
+                ..default()
+            },
+        ))
+        .with_children(|commands| {
+            commands.spawn(TextBundle::from_section(
+                "Waiting for the other player",
+                TextStyle {
+                    font_size: H1_FONT_SIZE,
+                    color: css::WHITE.into(),
+                    ..Default::default()
+                },
+            ));
+            commands.spawn(TextBundle::from_section(
+                "Press ESC to cancel",
+                TextStyle {
+                    font_size: BUTTON_FONT_SIZE,
+                    color: css::WHITE.into(),
+                    ..Default::default()
+                },
+            ));
+        });
+}
+
+fn cancel_on_esc(
+    mut keyboard_input_events: EventReader<KeyboardInput>,
+    mut next: ResMut<NextState<GameState>>,
+) {
+    for event in keyboard_input_events.read() {
+        if event.key_code == KeyCode::Escape && event.state == ButtonState::Pressed {
+            next.set(GameState::MainMenu);
+        }
+    }
+}
@@ -5,6 +5,7 @@ use bevy::{color::palettes::css, prelude::*};
 use crate::{
     assets::GameStartSettings,
     levels::GameLevelsSet,
+    rollback::RollbackApp,
     ui::{
         constants::{BACKDROP_COLOR, H1_FONT_SIZE, TITLE_FONT_SIZE},
         UiSet,
@@ -49,6 +50,7 @@ pub fn build_ui(app: &mut App) {
     let state = PlayState::CountdownBeforeRunning;
 
     app.init_resource::<CountdownTimer>()
+        .rollback_resource::<CountdownTimer>()
         .add_systems(
             OnEnter(state),
             (setup_coundown_time, spawn_ui)
@@ -56,6 +58,12 @@ pub fn build_ui(app: &mut App) {
                 .after(GameLevelsSet)
                 .in_set(UiSet),
         )
+        .add_systems(
+            FixedUpdate,
+            tick_countdown
+                .run_if(in_state(PlayState::CountdownBeforeRunning))
+                .in_set(UiSet),
+        )
         .add_systems(
             Update,
             update_countdown_text
@@ -152,17 +160,24 @@ fn spawn_ui(
     commands.entity(menu).insert_children(0, &[headline]);
 }
 
-fn update_countdown_text(
+/// Counts down in ticks of the game, like the gameplay itself.
+fn tick_countdown(
     time: Res<Time>,
-    mut query: Query<&mut Text, With<CountdownText>>,
     mut timer_res: ResMut<CountdownTimer>,
     mut next: ResMut<NextState<PlayState>>,
 ) {
-    if let Some(remaining) = timer_res.tick(&time) {
-        let mut text = query.single_mut();
-        text.sections[0].value = format!("{:1.1}", remaining.as_secs_f32());
-    } else {
+    if timer_res.tick(&time).is_none() {
         info!("Countdown finished");
         next.set(PlayState::Running);
     }
 }
+
+fn update_countdown_text(
+    mut query: Query<&mut Text, With<CountdownText>>,
+    timer_res: Res<CountdownTimer>,
+) {
+    if let Some(remaining) = timer_res.remaining() {
+        let mut text = query.single_mut();
+        text.sections[0].value = format!("{:1.1}", remaining.as_secs_f32());
+    }
+}
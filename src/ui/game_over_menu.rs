@@ -9,8 +9,8 @@ use bevy_persistent::Persistent;
 use strum::IntoEnumIterator;
 
 use crate::{
-    assets::{HighScoreBoard, HighScoreKey},
-    player::{Player, Score},
+    assets::{EndlessHighScoreBoard, GameMode, GameStartSettings, HighScoreBoard, HighScoreKey},
+    player::{Kills, Player, Score},
     states::GameOverReason,
     ui::{
         constants::{BUTTON_PADDING, H1_FONT_SIZE, H3_FONT_SIZE, MAX_NAME_LENGTH},
@@ -44,7 +44,7 @@ pub fn build_ui(app: &mut App) {
 
 fn spawn_ui(
     state: Res<State<PlayState>>,
-    score_query: Query<&Score, With<Player>>,
+    player_query: Query<(&Player, &Kills, &Score)>,
     highscore_key: Option<Res<HighScoreKey>>,
     mut asset_server: ResMut<AssetServer>,
     mut commands: Commands,
@@ -54,17 +54,30 @@ fn spawn_ui(
         _ => return,
     };
 
-    let _score = score_query.get_single().unwrap();
+    // the combined score of all local players
+    let score = Score::new(player_query.iter().map(|(_, _, score)| **score).sum());
     match highscore_key {
         Some(highscore_key) => setup_highscore_menu(
-            _score,
+            &score,
             &highscore_key,
             *state.get(),
             asset_server.as_mut(),
             &mut commands,
         ),
         None => setup_dead_menu(
-            gameover_reason,
+            match gameover_reason {
+                GameOverReason::PlayerDead => "Game Over".to_string(),
+                GameOverReason::GameWon => "Game Won".to_string(),
+                GameOverReason::TimeUp => "Time Up".to_string(),
+                GameOverReason::EscortLost => "Escort Lost".to_string(),
+                GameOverReason::Disconnected => "Player Left".to_string(),
+                GameOverReason::MatchWon => player_query
+                    .iter()
+                    .max_by_key(|(_, kills, score)| (***kills, ***score))
+                    .map_or("Match Over".to_string(), |(player, _, _)| {
+                        format!("Player {} Wins", player.index + 1)
+                    }),
+            },
             *state.get(),
             asset_server.as_mut(),
             &mut commands,
@@ -73,7 +86,7 @@ fn spawn_ui(
 }
 
 fn setup_dead_menu(
-    gameover_reason: GameOverReason,
+    title: String,
     state: PlayState,
     _asset_server: &mut AssetServer,
     commands: &mut Commands,
@@ -91,10 +104,7 @@ fn setup_dead_menu(
         .with_children(|cmd| {
             // title
             cmd.spawn(TextBundle::from_section(
-                match gameover_reason {
-                    GameOverReason::PlayerDead => "Game Over",
-                    GameOverReason::GameWon => "Game Won",
-                },
+                title,
                 TextStyle {
                     font_size: H1_FONT_SIZE,
                     color: Color::WHITE,
@@ -312,12 +322,15 @@ fn text_input_system(
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 fn handle_game_over_menu(
     mut event: EventReader<PressedEvent<GameOverButton>>,
     mut play_state: ResMut<NextState<PlayState>>,
     mut game_state: ResMut<NextState<GameState>>,
     mut highscore_board: ResMut<Persistent<HighScoreBoard>>,
+    mut endless_highscore_board: ResMut<Persistent<EndlessHighScoreBoard>>,
     mut highscore_key: Option<ResMut<HighScoreKey>>,
+    game_start_settings: Res<GameStartSettings>,
     text_query: Query<&Text, With<HighScorerName>>,
     mut commands: Commands,
 ) {
@@ -327,10 +340,17 @@ fn handle_game_over_menu(
         {
             commands.remove_resource::<HighScoreKey>();
             let name = name.sections.first().unwrap().value.trim();
-            highscore_board.assign_name(name, highscore_key);
-            highscore_board
-                .persist()
-                .expect("failed to persist high-scores");
+            if game_start_settings.mode == GameMode::Endless {
+                endless_highscore_board.assign_name(name, highscore_key);
+                endless_highscore_board
+                    .persist()
+                    .expect("failed to persist endless high-scores");
+            } else {
+                highscore_board.assign_name(name, highscore_key);
+                highscore_board
+                    .persist()
+                    .expect("failed to persist high-scores");
+            }
         };
 
         match id {
@@ -1,8 +1,13 @@
+use core::time::Duration;
+
 use bevy::{color::palettes::css, prelude::*};
 
 use crate::{
+    assets::{GameLevelSettings, GameMode, GameStartSettings, LevelObjective, PlayerSettings},
     asteroid::AsteroidCount,
-    player::{Player, Score},
+    escort::EscortHitPoints,
+    levels::{LevelTimer, ObjectiveProgress, Wave},
+    player::{JumpDrive, Kills, Player, Score, Shield},
     states::PlayState,
     ui::UiSet,
     GameLevel, GameState,
@@ -14,7 +19,19 @@ pub fn build_ui(app: &mut App) {
     app.add_systems(OnEnter(state), spawn_ui)
         .add_systems(
             Update,
-            (update_level_text, update_lives_text, update_asteroid_count)
+            (spawn_player_panels, despawn_player_panels)
+                .run_if(in_state(state))
+                .in_set(UiSet),
+        )
+        .add_systems(
+            Update,
+            (
+                update_level_text,
+                update_lives_text,
+                update_asteroid_count,
+                update_time_text,
+                update_objective_text,
+            )
                 .run_if(in_state(PlayState::CountdownBeforeRunning))
                 .in_set(UiSet),
         )
@@ -23,8 +40,13 @@ pub fn build_ui(app: &mut App) {
             (
                 update_level_text,
                 update_score_text,
+                update_kills_text,
                 update_lives_text,
                 update_asteroid_count,
+                update_shield_bar,
+                update_jump_text,
+                update_time_text,
+                update_objective_text,
             )
                 .run_if(in_state(PlayState::Running))
                 .in_set(UiSet),
@@ -35,13 +57,47 @@ pub fn build_ui(app: &mut App) {
 struct LevelText;
 
 #[derive(Component, Debug, Clone)]
-struct ScoreText;
+struct AsteroidText;
 
 #[derive(Component, Debug, Clone)]
-struct LivesText;
+struct TimeText;
 
 #[derive(Component, Debug, Clone)]
-struct AsteroidText;
+struct ObjectiveText;
+
+/// Container holding one [`PlayerPanel`] per player
+#[derive(Component, Debug, Clone)]
+struct PlayerPanels;
+
+#[derive(Component, Debug, Clone)]
+struct PlayerPanel {
+    player: Entity,
+}
+
+#[derive(Component, Debug, Clone)]
+struct ScoreText {
+    player: Entity,
+}
+
+#[derive(Component, Debug, Clone)]
+struct KillsText {
+    player: Entity,
+}
+
+#[derive(Component, Debug, Clone)]
+struct LivesText {
+    player: Entity,
+}
+
+#[derive(Component, Debug, Clone)]
+struct ShieldBar {
+    player: Entity,
+}
+
+#[derive(Component, Debug, Clone)]
+struct JumpText {
+    player: Entity,
+}
 
 fn spawn_ui(mut commands: Commands) {
     debug!("spawning game ui");
@@ -113,10 +169,10 @@ fn spawn_ui(mut commands: Commands) {
                         .spawn(NodeBundle::default())
                         .with_children(|commands| {
                             commands.spawn((
-                                Name::new("Score Display"),
+                                Name::new("Asteroids Counter"),
                                 TextBundle::from_sections([
                                     TextSection::new(
-                                        "Score: ",
+                                        "Asteroids: ",
                                         TextStyle {
                                             //font: asset_server.load("fonts/FiraSans-Bold.ttf"),
                                             font_size: 20.0,
@@ -129,12 +185,12 @@ fn spawn_ui(mut commands: Commands) {
                                         TextStyle {
                                             //font: asset_server.load("fonts/FiraSans-Bold.ttf"),
                                             font_size: 20.0,
-                                            color: css::RED.into(),
+                                            color: css::LIGHT_BLUE.into(),
                                             ..Default::default()
                                         },
                                     ),
                                 ]),
-                                ScoreText,
+                                AsteroidText,
                             ));
                         });
                     commands.spawn(NodeBundle {
@@ -144,105 +200,287 @@ fn spawn_ui(mut commands: Commands) {
                         },
                         ..default()
                     });
-                    commands
-                        .spawn(NodeBundle::default())
-                        .with_children(|commands| {
-                            commands.spawn((
-                                Name::new("Lives Display"),
-                                TextBundle::from_sections([
-                                    TextSection::new(
-                                        "Lives: ",
-                                        TextStyle {
-                                            //font: asset_server.load("fonts/FiraSans-Bold.ttf"),
-                                            font_size: 20.0,
-                                            color: css::WHITE.into(),
-                                            ..Default::default()
-                                        },
-                                    ),
-                                    TextSection::new(
-                                        "0",
-                                        TextStyle {
-                                            //font: asset_server.load("fonts/FiraSans-Bold.ttf"),
-                                            font_size: 20.0,
-                                            color: css::GOLD.into(),
-                                            ..Default::default()
-                                        },
-                                    ),
-                                ]),
-                                LivesText,
-                            ));
-                        });
+                    commands.spawn((
+                        Name::new("Level Timer"),
+                        hud_text("Time: ", "0:00", css::GOLD),
+                        TimeText,
+                    ));
+                    commands.spawn(NodeBundle {
+                        style: Style {
+                            width: Val::VMin(10.0),
+                            ..default()
+                        },
+                        ..default()
+                    });
+                    commands.spawn((
+                        Name::new("Level Objective"),
+                        hud_text("Objective: ", "", css::ORANGE),
+                        ObjectiveText,
+                    ));
                 });
 
-            // Bottom row
-            commands
-                .spawn(NodeBundle {
+            // Bottom row, filled with one panel per player
+            commands.spawn((
+                Name::new("Player Panels"),
+                NodeBundle {
                     style: Style {
                         width: Val::Percent(100.0),
                         display: Display::Flex,
                         flex_direction: FlexDirection::Row,
-                        justify_content: JustifyContent::Center,
+                        justify_content: JustifyContent::SpaceEvenly,
                         align_self: AlignSelf::FlexEnd,
                         ..default()
                     },
                     ..default()
-                })
+                },
+                PlayerPanels,
+            ));
+        });
+}
+
+fn spawn_player_panels(
+    mut commands: Commands,
+    panels: Query<Entity, With<PlayerPanels>>,
+    players: Query<(Entity, &Player), Added<Player>>,
+    game_start_settings: Res<GameStartSettings>,
+) {
+    let Ok(panels) = panels.get_single() else {
+        return;
+    };
+    let mut players = players.iter().collect::<Vec<_>>();
+    players.sort_by_key(|(_, player)| player.index);
+    for (player, Player { index, lives }) in players {
+        debug!(?player, index, "spawning player panel");
+        commands.entity(panels).with_children(|commands| {
+            commands
+                .spawn((
+                    Name::new(format!("Player {} Panel", index + 1)),
+                    NodeBundle {
+                        style: Style {
+                            display: Display::Flex,
+                            flex_direction: FlexDirection::Column,
+                            align_items: AlignItems::FlexStart,
+                            ..default()
+                        },
+                        ..default()
+                    },
+                    PlayerPanel { player },
+                ))
                 .with_children(|commands| {
+                    commands.spawn(TextBundle::from_section(
+                        format!("P{}", index + 1),
+                        TextStyle {
+                            //font: asset_server.load("fonts/FiraSans-Bold.ttf"),
+                            font_size: 20.0,
+                            color: css::GREEN.into(),
+                            ..Default::default()
+                        },
+                    ));
+                    commands.spawn((hud_text("Score: ", "0", css::RED), ScoreText { player }));
+                    if game_start_settings.mode == GameMode::Versus {
+                        commands
+                            .spawn((hud_text("Kills: ", "0", css::ORANGE), KillsText { player }));
+                    }
+                    commands.spawn((
+                        hud_text("Lives: ", lives.to_string(), css::GOLD),
+                        LivesText { player },
+                    ));
                     commands
-                        .spawn(NodeBundle::default())
+                        .spawn(NodeBundle {
+                            style: Style {
+                                align_items: AlignItems::Center,
+                                ..default()
+                            },
+                            ..default()
+                        })
                         .with_children(|commands| {
-                            commands.spawn((
-                                Name::new("Asteroids Counter"),
-                                TextBundle::from_sections([
-                                    TextSection::new(
-                                        "Asteroids: ",
-                                        TextStyle {
-                                            //font: asset_server.load("fonts/FiraSans-Bold.ttf"),
-                                            font_size: 20.0,
-                                            color: css::WHITE.into(),
-                                            ..Default::default()
-                                        },
-                                    ),
-                                    TextSection::new(
-                                        "0",
-                                        TextStyle {
-                                            //font: asset_server.load("fonts/FiraSans-Bold.ttf"),
-                                            font_size: 20.0,
-                                            color: css::LIGHT_BLUE.into(),
-                                            ..Default::default()
-                                        },
-                                    ),
-                                ]),
-                                AsteroidText,
+                            commands.spawn(TextBundle::from_section(
+                                "Shield: ",
+                                TextStyle {
+                                    //font: asset_server.load("fonts/FiraSans-Bold.ttf"),
+                                    font_size: 20.0,
+                                    color: css::WHITE.into(),
+                                    ..Default::default()
+                                },
                             ));
+                            commands
+                                .spawn(NodeBundle {
+                                    style: Style {
+                                        width: Val::Px(100.0),
+                                        height: Val::Px(10.0),
+                                        ..default()
+                                    },
+                                    background_color: css::DARK_SLATE_GRAY.into(),
+                                    ..default()
+                                })
+                                .with_children(|commands| {
+                                    commands.spawn((
+                                        NodeBundle {
+                                            style: Style {
+                                                width: Val::Percent(100.0),
+                                                height: Val::Percent(100.0),
+                                                ..default()
+                                            },
+                                            background_color: css::DEEP_SKY_BLUE.into(),
+                                            ..default()
+                                        },
+                                        ShieldBar { player },
+                                    ));
+                                });
                         });
+                    commands.spawn((
+                        hud_text("Jump: ", "Ready", css::VIOLET),
+                        JumpText { player },
+                    ));
                 });
         });
+    }
+}
+
+fn despawn_player_panels(
+    mut commands: Commands,
+    panels: Query<(Entity, &PlayerPanel)>,
+    mut removed: RemovedComponents<Player>,
+) {
+    for player in removed.read() {
+        for (panel, _) in panels.iter().filter(|(_, panel)| panel.player == player) {
+            debug!(?player, "despawning player panel");
+            commands.entity(panel).despawn_recursive();
+        }
+    }
 }
 
-fn update_level_text(mut query: Query<&mut Text, With<LevelText>>, level: Res<GameLevel>) {
+fn hud_text(label: &str, value: impl Into<String>, color: Srgba) -> TextBundle {
+    TextBundle::from_sections([
+        TextSection::new(
+            label,
+            TextStyle {
+                //font: asset_server.load("fonts/FiraSans-Bold.ttf"),
+                font_size: 20.0,
+                color: css::WHITE.into(),
+                ..Default::default()
+            },
+        ),
+        TextSection::new(
+            value,
+            TextStyle {
+                //font: asset_server.load("fonts/FiraSans-Bold.ttf"),
+                font_size: 20.0,
+                color: color.into(),
+                ..Default::default()
+            },
+        ),
+    ])
+}
+fn update_level_text(
+    mut query: Query<&mut Text, With<LevelText>>,
+    level: Res<GameLevel>,
+    wave: Res<Wave>,
+    game_start_settings: Res<GameStartSettings>,
+) {
     for mut text in query.iter_mut() {
-        text.sections[1].value = (**level).to_string();
+        // the generated levels of the endless mode are just numbered
+        let (label, value) = match game_start_settings.mode {
+            GameMode::Endless => ("Wave: ", (**wave).to_string()),
+            GameMode::Campaign | GameMode::Versus | GameMode::TimeAttack => {
+                ("Level: ", (**level).to_string())
+            }
+        };
+        text.sections[0].value = label.to_string();
+        text.sections[1].value = value;
     }
 }
 
+/// Shows the time spent in the level, or the time left in [GameMode::TimeAttack].
+fn update_time_text(
+    mut query: Query<&mut Text, With<TimeText>>,
+    level_timer: Res<LevelTimer>,
+    level_settings: Res<GameLevelSettings>,
+    game_start_settings: Res<GameStartSettings>,
+) {
+    let time = match level_settings.time_limit {
+        Some(limit) if game_start_settings.mode == GameMode::TimeAttack => {
+            limit.saturating_sub(level_timer.elapsed())
+        }
+        _ => level_timer.elapsed(),
+    };
+    for mut text in query.iter_mut() {
+        text.sections[1].value = format_time(time);
+    }
+}
+
+/// Shows what is left to do to clear the level.
+fn update_objective_text(
+    mut query: Query<&mut Text, With<ObjectiveText>>,
+    level_settings: Res<GameLevelSettings>,
+    level_timer: Res<LevelTimer>,
+    objective_progress: Res<ObjectiveProgress>,
+    escort_query: Query<&EscortHitPoints>,
+) {
+    let objective = match &level_settings.objective {
+        LevelObjective::ClearAll => "Clear all asteroids".to_string(),
+        LevelObjective::Survive(duration) => format!(
+            "Survive {}",
+            format_time(duration.saturating_sub(level_timer.elapsed()))
+        ),
+        LevelObjective::Score(points) => {
+            format!(
+                "Score {}/{}",
+                objective_progress.points.min(*points),
+                points
+            )
+        }
+        LevelObjective::DestroyFromPool { count, .. } => format!(
+            "Destroy {}/{}",
+            objective_progress.destroyed.min(*count),
+            count
+        ),
+        LevelObjective::Escort { duration, .. } => format!(
+            "Escort {} ({} HP)",
+            format_time(duration.saturating_sub(level_timer.elapsed())),
+            escort_query
+                .get_single()
+                .map_or(0, |hit_points| **hit_points)
+        ),
+    };
+    for mut text in query.iter_mut() {
+        text.sections[1].value.clone_from(&objective);
+    }
+}
+
+fn format_time(time: Duration) -> String {
+    let seconds = time.as_secs();
+    format!("{}:{:02}", seconds / 60, seconds % 60)
+}
+
 fn update_score_text(
-    mut query: Query<&mut Text, With<ScoreText>>,
+    mut query: Query<(&mut Text, &ScoreText)>,
     score: Query<&Score, Changed<Score>>,
 ) {
-    if let Ok(score) = score.get_single() {
-        for mut text in query.iter_mut() {
+    for (mut text, ScoreText { player }) in query.iter_mut() {
+        if let Ok(score) = score.get(*player) {
             text.sections[1].value = format!("{}", **score);
         }
     }
 }
 
+fn update_kills_text(
+    mut query: Query<(&mut Text, &KillsText)>,
+    kills: Query<&Kills, Changed<Kills>>,
+) {
+    for (mut text, KillsText { player }) in query.iter_mut() {
+        if let Ok(kills) = kills.get(*player) {
+            text.sections[1].value = format!("{}", **kills);
+        }
+    }
+}
+
 fn update_lives_text(
-    mut query: Query<&mut Text, With<LivesText>>,
-    score: Query<&Player, Changed<Player>>,
+    mut query: Query<(&mut Text, &LivesText)>,
+    players: Query<&Player, Changed<Player>>,
 ) {
-    if let Ok(player) = score.get_single() {
-        for mut text in query.iter_mut() {
+    for (mut text, LivesText { player }) in query.iter_mut() {
+        if let Ok(player) = players.get(*player) {
             text.sections[1].value = format!("{}", player.lives);
         }
     }
@@ -256,3 +494,43 @@ fn update_asteroid_count(
         text.sections[1].value = format!("{}", **counter);
     }
 }
+
+fn update_shield_bar(
+    mut query: Query<(&mut Style, &ShieldBar)>,
+    shield: Query<&Shield, (With<Player>, Changed<Shield>)>,
+    player_settings: Res<PlayerSettings>,
+) {
+    for (mut style, ShieldBar { player }) in query.iter_mut() {
+        if let Ok(shield) = shield.get(*player) {
+            let percent = if player_settings.shield_capacity > 0.0 {
+                (shield.energy / player_settings.shield_capacity * 100.0).clamp(0.0, 100.0)
+            } else {
+                0.0
+            };
+            style.width = Val::Percent(percent);
+        }
+    }
+}
+
+fn update_jump_text(
+    mut query: Query<(&mut Text, &JumpText)>,
+    jump_drive: Query<&JumpDrive, With<Player>>,
+) {
+    for (mut text, JumpText { player }) in query.iter_mut() {
+        let Ok(jump_drive) = jump_drive.get(*player) else {
+            text.sections[1].value = "-".to_string();
+            continue;
+        };
+        let status = if jump_drive.jumps_left == Some(0) {
+            "None left".to_string()
+        } else if jump_drive.cooldown.finished() {
+            "Ready".to_string()
+        } else {
+            format!("{:.1}s", jump_drive.cooldown.remaining_secs())
+        };
+        text.sections[1].value = match jump_drive.jumps_left {
+            Some(jumps_left) if jumps_left > 0 => format!("{status} ({jumps_left})"),
+            _ => status,
+        };
+    }
+}
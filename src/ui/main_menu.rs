@@ -1,9 +1,10 @@
 use bevy::prelude::*;
 
 use crate::{
+    assets::{GameMode, GameStartSettings},
     ui::{
         constants::H1_FONT_SIZE,
-        interaction::{InteractionHandlerExt, InteractionId, PressedEvent},
+        interaction::{InteractionHandlerExt, InteractionId, InteractionIdComponent, PressedEvent},
         UiSet,
     },
     GameState,
@@ -12,6 +13,11 @@ use crate::{
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub enum MainMenuButton {
     Play,
+    PlayCoop,
+    PlayVersus,
+    PlayEndless,
+    PlayTimeAttack,
+    Difficulty,
     HighScore,
     Quit,
 }
@@ -24,7 +30,10 @@ pub fn build_ui(app: &mut App) {
         .add_interaction_handler_in_state::<MainMenuButton>(state)
         .add_systems(
             Update,
-            handle_main_menu.run_if(in_state(state)).in_set(UiSet),
+            (handle_main_menu, update_difficulty_button)
+                .chain()
+                .run_if(in_state(state))
+                .in_set(UiSet),
         );
 }
 
@@ -34,6 +43,11 @@ fn spawn_ui(mut commands: Commands) {
         GameState::MainMenu,
         [
             ("Play", MainMenuButton::Play),
+            ("Play Co-op", MainMenuButton::PlayCoop),
+            ("Play Versus", MainMenuButton::PlayVersus),
+            ("Play Endless", MainMenuButton::PlayEndless),
+            ("Time Attack", MainMenuButton::PlayTimeAttack),
+            ("Difficulty", MainMenuButton::Difficulty),
             ("Highscores", MainMenuButton::HighScore),
             ("Quit", MainMenuButton::Quit),
         ]
@@ -64,13 +78,40 @@ fn spawn_ui(mut commands: Commands) {
 fn handle_main_menu(
     mut event: EventReader<PressedEvent<MainMenuButton>>,
     mut next_state: ResMut<NextState<GameState>>,
+    mut game_start: ResMut<GameStartSettings>,
     mut exit: EventWriter<AppExit>,
 ) {
     for PressedEvent { id, entity: _ } in event.read() {
         match id {
             MainMenuButton::Play => {
+                game_start.mode = GameMode::Campaign;
+                game_start.players = 1;
                 next_state.set(GameState::Playing);
             }
+            MainMenuButton::PlayCoop => {
+                game_start.mode = GameMode::Campaign;
+                // keep a larger number of players, e.g. given on the command line
+                game_start.players = game_start.players.max(2);
+                next_state.set(GameState::Playing);
+            }
+            MainMenuButton::PlayVersus => {
+                game_start.mode = GameMode::Versus;
+                game_start.players = game_start.players.max(2);
+                next_state.set(GameState::Playing);
+            }
+            MainMenuButton::PlayEndless => {
+                game_start.mode = GameMode::Endless;
+                game_start.players = 1;
+                next_state.set(GameState::Playing);
+            }
+            MainMenuButton::PlayTimeAttack => {
+                game_start.mode = GameMode::TimeAttack;
+                game_start.players = 1;
+                next_state.set(GameState::Playing);
+            }
+            MainMenuButton::Difficulty => {
+                game_start.difficulty = game_start.difficulty.next();
+            }
             MainMenuButton::HighScore => {
                 next_state.set(GameState::HighscoreMenu);
             }
@@ -80,3 +121,23 @@ fn handle_main_menu(
         }
     }
 }
+
+/// Shows the chosen difficulty on its button.
+fn update_difficulty_button(
+    game_start: Res<GameStartSettings>,
+    button_query: Query<(&InteractionIdComponent<MainMenuButton>, &Children)>,
+    mut text_query: Query<&mut Text>,
+) {
+    let label = format!("Difficulty: {}", game_start.difficulty);
+    for (InteractionIdComponent(id), children) in button_query.iter() {
+        if *id != MainMenuButton::Difficulty {
+            continue;
+        }
+        let mut texts = text_query.iter_many_mut(children);
+        while let Some(mut text) = texts.fetch_next() {
+            if text.sections[0].value != label {
+                text.sections[0].value.clone_from(&label);
+            }
+        }
+    }
+}
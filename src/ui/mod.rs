@@ -1,6 +1,8 @@
 #[macro_use]
 mod menu;
 
+#[cfg(feature = "cmd_line")]
+mod connecting_ui;
 mod constants;
 mod countdown_ui;
 mod game_over_menu;
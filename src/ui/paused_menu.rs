@@ -48,6 +48,13 @@ pub fn build_ui(app: &mut App) {
                 .run_if(in_state(PlayState::Paused))
                 .in_set(UiSet),
         )
+        .add_systems(
+            OnTransition {
+                exited: PlayState::Paused,
+                entered: PlayState::CountdownBeforeRunning,
+            },
+            setup_resume_countdown.in_set(UiSet),
+        )
         .observe(on_resume_event)
         .observe(on_pause_event);
 }
@@ -91,9 +98,13 @@ fn handle_paused_menu(mut event: EventReader<PressedEvent<PausedButton>>, mut co
     }
 }
 
-fn on_resume_event(
-    _trigger: Trigger<ResumeEvent>,
-    mut play_state: ResMut<NextState<PlayState>>,
+fn on_resume_event(_trigger: Trigger<ResumeEvent>, mut play_state: ResMut<NextState<PlayState>>) {
+    play_state.set(PlayState::CountdownBeforeRunning);
+}
+
+/// Counts down at least the minimum time before the game continues, in the transition instead
+/// of the [ResumeEvent], so a network game resumes the same on both sides.
+fn setup_resume_countdown(
     timer: Res<CountdownTimer>,
     game_start: Res<GameStartSettings>,
     mut commands: Commands,
@@ -111,7 +122,6 @@ fn on_resume_event(
         }
         _ => {}
     };
-    play_state.set(PlayState::CountdownBeforeRunning);
 }
 
 fn on_pause_event(_trigger: Trigger<PauseEvent>, mut next_state: ResMut<NextState<PlayState>>) {
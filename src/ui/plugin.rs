@@ -15,6 +15,9 @@ impl Plugin for UiPlugin {
         super::game_over_menu::build_ui(app);
         super::highscore_ui::build_ui(app);
 
+        #[cfg(feature = "cmd_line")]
+        super::connecting_ui::build_ui(app);
+
         #[cfg(feature = "dbg_colliders")]
         super::dbg_colliders::build_ui(app);
     }
@@ -20,3 +20,31 @@ impl RngComponentExt for RngComponent {
         self.f32() * (end - start) + start
     }
 }
+
+/// Grouping like [itertools::Itertools::into_group_map], but keeping the groups in the order their
+/// first item came in. Unlike the order of a hash map, that is the same on every run, which keeps
+/// a game reproducible from its seed.
+pub trait IntoOrderedGroups: Iterator + Sized {
+    fn into_ordered_groups<K: PartialEq, V>(self) -> Vec<(K, Vec<V>)>
+    where
+        Self: Iterator<Item = (K, V)>,
+    {
+        let mut groups: Vec<(K, Vec<V>)> = Vec::new();
+        for (key, value) in self {
+            match groups.iter_mut().find(|(group, _)| *group == key) {
+                Some((_, values)) => values.push(value),
+                None => groups.push((key, vec![value])),
+            }
+        }
+        groups
+    }
+
+    fn into_ordered_groups_by<K: PartialEq>(
+        self,
+        mut key: impl FnMut(&Self::Item) -> K,
+    ) -> Vec<(K, Vec<Self::Item>)> {
+        self.map(|item| (key(&item), item)).into_ordered_groups()
+    }
+}
+
+impl<I: Iterator> IntoOrderedGroups for I {}